@@ -1,76 +1,137 @@
 use crate::attributes::AttributeType;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
 use syn::{
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream},
-    punctuated::Punctuated,
-    Attribute, Error, Meta, Result, Token,
+    token, Attribute, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Result, Token,
 };
-struct AttributesAttribute {
-    name: Ident,
-    _equals: Token!(=),
-    attribute: Punctuated<Vec<Attribute>, Token!(,)>,
+
+/// `attribute = #[atts]` where any further `, #[atts]` are also part of the same item
+fn parse_attributes(name: Ident, input: ParseStream) -> Result<Vec<Result<AttributeType>>> {
+    let mut atts = input.call(Attribute::parse_outer)?;
+    while input.peek(Token![,]) && input.peek2(Token![#]) {
+        input.parse::<Token![,]>()?;
+        atts.extend(input.call(Attribute::parse_outer)?);
+    }
+    let tokens = quote::quote! { #(#atts) * };
+    Ok(vec![Ok(if name == AttributeType::ATTRIBUTE {
+        AttributeType::Attributes(tokens)
+    } else {
+        AttributeType::AttributesReplace(tokens)
+    })])
 }
 
-impl Parse for AttributesAttribute {
-    fn parse(input: ParseStream) -> Result<AttributesAttribute> {
-        let name = input.parse::<Ident>().and_then(|x| {
-            if x == AttributeType::ATTRIBUTE || x == AttributeType::ATTRIBUTE_REPLACE {
-                Ok(x)
-            } else {
-                Err(syn::Error::new(
-                    x.span(),
+/// A single option which is one of `flag`, `key = "str"`, `key(..)`, `attribute = #[atts]` or
+/// `replace = "from" => "to"`
+fn parse_option(input: ParseStream) -> Result<Vec<Result<AttributeType>>> {
+    if input.peek(Lit) {
+        let lit = input.parse()?;
+        return Ok(vec![AttributeType::new(&NestedMeta::Lit(lit))]);
+    }
+    // Keywords are parsed too since `for` is an option
+    let name = match input.call(Ident::parse_any) {
+        Ok(name) => name,
+        Err(_) => {
+            return Err(Error::new(
+                input.span(),
+                "Expected an option like `trim_end = \"Signal\"`",
+            ))
+        }
+    };
+    let meta = if input.peek(Token![=]) {
+        let eq_token = input.parse()?;
+        let is_attribute =
+            name == AttributeType::ATTRIBUTE || name == AttributeType::ATTRIBUTE_REPLACE;
+        if input.peek(Token![#]) {
+            if is_attribute {
+                return parse_attributes(name, input);
+            }
+            return Err(Error::new(
+                name.span(),
+                format!(
+                    "Only `{}` & `{}` can be given attributes like `#[derive(Debug)]`",
+                    AttributeType::ATTRIBUTE,
+                    AttributeType::ATTRIBUTE_REPLACE
+                ),
+            ));
+        }
+        if is_attribute {
+            return Err(Error::new(
+                input.span(),
+                format!("Expected attributes like `{} = #[derive(Debug)]`", name),
+            ));
+        } else if !input.peek(Lit) {
+            return Err(Error::new(
+                input.span(),
+                format!("Expected a string literal after `{} =`", name),
+            ));
+        }
+        let lit = input.parse()?;
+        if input.peek(Token![=>]) {
+            let arrow = input.parse::<Token![=>]>()?;
+            if name != AttributeType::REPLACE {
+                return Err(Error::new(
+                    arrow.spans[0],
                     format!(
-                        "Designal only expects tokens in this form for use with `{} = #[atts]`",
-                        AttributeType::ATTRIBUTE
+                        "Only `{}` can be given a replacement like `\"Signal\" => \"Dto\"`",
+                        AttributeType::REPLACE
                     ),
-                ))
+                ));
+            } else if !input.peek(Lit) {
+                return Err(Error::new(
+                    input.span(),
+                    "Expected the string to replace it with",
+                ));
             }
-        });
-        Ok(AttributesAttribute {
-            name: name?,
-            _equals: input.parse()?,
-            attribute: input.parse_terminated(Attribute::parse_outer)?,
+            let to = input.parse()?;
+            return Ok(vec![AttributeType::make_replace(&name, &lit, &to)]);
+        }
+        Meta::NameValue(MetaNameValue {
+            path: name.into(),
+            eq_token,
+            lit,
         })
-    }
+    } else if input.peek(token::Paren) {
+        let content;
+        Meta::List(MetaList {
+            path: name.into(),
+            paren_token: parenthesized!(content in input),
+            nested: content.parse_terminated(NestedMeta::parse)?,
+        })
+    } else {
+        Meta::Path(name.into())
+    };
+    Ok(vec![AttributeType::new(&NestedMeta::Meta(meta))])
 }
 
-// TODO: Allow combining of two types
-// At the moment this can't be done:
-// #[designal(trim_end = "Signal", attribute = #[derive(Debug)])]
-// Also / switch to use of string?
-// #[designal(attribute = "#[derive(Debug)]", trim_end = "Bean")]
-pub(crate) fn parse(att: &Attribute) -> Vec<Result<AttributeType>> {
-    match att.parse_meta() {
-        Ok(meta) => match meta {
-            syn::Meta::List(meta_list) => meta_list.nested.iter().map(AttributeType::new).collect(),
-            Meta::Path(p) => vec![Err(Error::new(
-                p.segments[0].ident.span(),
-                "Unsupported attribute type",
-            ))],
-            Meta::NameValue(nv) => {
-                vec![Err(Error::new(nv.lit.span(), "Unsupported attribute type"))]
+/// The options before any syntax error are still returned so all their errors can be reported
+fn parse_options(input: ParseStream) -> Result<Vec<Result<AttributeType>>> {
+    let mut options = Vec::new();
+    while !input.is_empty() {
+        let option = parse_option(input).and_then(|option| {
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
             }
-        },
-        // TODO: Can i combine the span errors here?
-        Err(_) => match att.parse_args::<AttributesAttribute>() {
-            Ok(t) => t
-                .attribute
-                .iter()
-                .map(|a| {
-                    // Validated earlier in the parsing
-                    if t.name == AttributeType::ATTRIBUTE {
-                        Ok(AttributeType::Attributes(quote::quote! { #(#a) *}))
-                    } else {
-                        Ok(AttributeType::AttributesReplace(quote::quote! { #(#a) *}))
-                    }
-                })
-                .collect(),
+            Ok(option)
+        });
+        match option {
+            Ok(option) => options.extend(option),
             Err(e) => {
-                vec![Err(Error::new(
-                    e.span(),
-                    "Could not parse Designal the attributes",
-                ))]
+                options.push(Err(e));
+                // Nothing after the error can be trusted
+                input.parse::<TokenStream>()?;
             }
-        },
+        }
+    }
+    Ok(options)
+}
+
+/// Any mix of the options can be used in a single attribute eg.
+/// `#[designal(trim_end = "Signal", attribute = #[derive(Debug)], keep_rc)]`
+pub(crate) fn parse(att: &Attribute) -> Vec<Result<AttributeType>> {
+    match att.parse_args_with(parse_options) {
+        Ok(options) => options,
+        Err(e) => vec![Err(e)],
     }
 }
@@ -1,11 +1,15 @@
-use crate::builder::Naming;
+use crate::builder::{Naming, Wrapper};
+use crate::errors::Errors;
 use proc_macro2::{Span, TokenStream};
 use quote::format_ident;
-use syn::{Attribute, Error, Ident, Meta, MetaNameValue, NestedMeta, Result};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Data, DeriveInput, Error, Field, Generics,
+    Ident, Meta, MetaList, MetaNameValue, NestedMeta, Path, Result, Token, Visibility,
+    WherePredicate,
+};
 
 // TODO: Add attribute filter
 // TODO: Add attribute adder
-// TODO: Add generics, where, lifetimes filter
 
 // Derive macro can't see the other derives, so there is no way to automatically parse them on for the struct.
 // For a field it is possible
@@ -15,13 +19,48 @@ pub(crate) enum AttributeType {
     Rename(String, Span),
     AddStart(String, Span),
     AddEnd(String, Span),
+    AddStartAll(String, Span),
+    AddEndAll(String, Span),
+    For(Vec<(String, Span)>),
+    RenameMap(Vec<(String, String, Span)>),
     TrimStart(String, Span),
     TrimStartAll(String, Span),
     TrimEnd(String, Span),
     TrimEndAll(String, Span),
+    TrimStartRepeat(String, Span),
+    TrimEndRepeat(String, Span),
+    RenameAll(Case, Span),
+    Replace(String, String, Span),
+    FieldRenamer(Renamer),
     KeepRc(Span),
     KeepArc(Span),
+    KeepBox(Span),
+    KeepCell(Span),
+    KeepRefCell(Span),
+    KeepWeak(Span),
+    KeepCow(Span),
+    KeepReadOnly(Span),
     HashMap(Span),
+    Snapshot(Span),
+    Rebuild(Span),
+    Apply(Span),
+    ApplyNeq(Span),
+    Default(Path, Span),
+    Vis(Visibility, Span),
+    FieldVis(Visibility, Span),
+    Generics(Generics, Span),
+    Repr(TokenStream, Span),
+    MutableVec(Span),
+    MutableMap(Span),
+    Rc(Span),
+    Nested(Span),
+    SkipApply(Span),
+    Unwrap(String, Span),
+    Map(String, Path, Span),
+    Alias(Vec<(String, Wrapper, Span)>),
+    RenameExclude(Vec<(String, Span)>),
+    Bound(Vec<WherePredicate>),
+    RemoveBound(Vec<(String, Span)>),
     Attributes(TokenStream),
     AttributesReplace(TokenStream),
 }
@@ -32,13 +71,53 @@ impl AttributeType {
     const RENAME: &'static str = "rename";
     const ADD_START: &'static str = "add_start";
     const ADD_END: &'static str = "add_end";
+    const ADD_START_ALL: &'static str = "add_start_all";
+    const ADD_END_ALL: &'static str = "add_end_all";
+    const FOR: &'static str = "for";
+    const RENAME_MAP: &'static str = "rename_map";
     const TRIM_START: &'static str = "trim_start";
     const TRIM_START_ALL: &'static str = "trim_start_all";
     const TRIM_END: &'static str = "trim_end";
     const TRIM_END_ALL: &'static str = "trim_end_all";
+    const TRIM_START_REPEAT: &'static str = "trim_start_repeat";
+    const TRIM_END_REPEAT: &'static str = "trim_end_repeat";
+    const RENAME_ALL: &'static str = "rename_all";
+    pub(crate) const REPLACE: &'static str = "replace";
+    const FIELD_NAME: &'static str = "field_name";
+    const FIELD_CASE: &'static str = "field_case";
+    const ADD_FIELD_START: &'static str = "add_field_start";
+    const ADD_FIELD_END: &'static str = "add_field_end";
+    const TRIM_FIELD_START: &'static str = "trim_field_start";
+    const TRIM_FIELD_END: &'static str = "trim_field_end";
     const KEEP_RC: &'static str = "keep_rc";
     const KEEP_ARC: &'static str = "keep_arc";
+    const KEEP_BOX: &'static str = "keep_box";
+    const KEEP_CELL: &'static str = "keep_cell";
+    const KEEP_REFCELL: &'static str = "keep_refcell";
+    const KEEP_WEAK: &'static str = "keep_weak";
+    const KEEP_COW: &'static str = "keep_cow";
+    const KEEP_READ_ONLY: &'static str = "keep_read_only";
     const HASHMAP: &'static str = "hashmap";
+    const SNAPSHOT: &'static str = "snapshot";
+    const REBUILD: &'static str = "rebuild";
+    const APPLY: &'static str = "apply";
+    const APPLY_NEQ: &'static str = "apply_neq";
+    const DEFAULT: &'static str = "default";
+    const VIS: &'static str = "vis";
+    const FIELD_VIS: &'static str = "field_vis";
+    const GENERICS: &'static str = "generics";
+    const REPR: &'static str = "repr";
+    const MUTABLE_VEC: &'static str = "mutable_vec";
+    const MUTABLE_MAP: &'static str = "mutable_map";
+    const RC: &'static str = "rc";
+    const NESTED: &'static str = "nested";
+    const SKIP_APPLY: &'static str = "skip_apply";
+    const UNWRAP: &'static str = "unwrap";
+    const MAP: &'static str = "map";
+    const ALIAS: &'static str = "alias";
+    const RENAME_EXCLUDE: &'static str = "rename_exclude";
+    const BOUND: &'static str = "bound";
+    const REMOVE_BOUND: &'static str = "remove_bound";
     pub(crate) const ATTRIBUTE: &'static str = "attribute";
     pub(crate) const ATTRIBUTE_REPLACE: &'static str = "attribute_replace";
 
@@ -50,6 +129,170 @@ impl AttributeType {
         Err(Error::new(span,"Attribute option was not a single identifier: the path had more than a single segement."))
     }
 
+    fn err_map(span: Span) -> Result<Self> {
+        Err(Error::new(
+            span,
+            "map must replace a type with another like `map = \"MyVec => Vec\"`",
+        ))
+    }
+
+    /// eg. `alias(M = "Mutable", Shared = "Rc")`
+    fn make_alias(list: &MetaList) -> Result<Self> {
+        let err_alias = |span: Span| {
+            Error::new(
+                span,
+                "alias must name a wrapper like `alias(M = \"Mutable\")`",
+            )
+        };
+        if list.nested.is_empty() {
+            return Err(err_alias(list.paren_token.span));
+        }
+        let alias = |nested: &NestedMeta| match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) => match (nv.path.get_ident(), &nv.lit) {
+                (Some(name), syn::Lit::Str(s)) => match Wrapper::from_name(&s.value()) {
+                    Some(wrapper) => Ok((name.to_string(), wrapper, name.span())),
+                    None => {
+                        let names: Vec<String> = Wrapper::ALL
+                            .iter()
+                            .map(|w| format!("`{}`", w.name()))
+                            .collect();
+                        Err(Error::new(
+                            s.span(),
+                            format!("An alias can only be one of {}", names.join(", ")),
+                        ))
+                    }
+                },
+                _ => Err(err_alias(nv.span())),
+            },
+            nested => Err(err_alias(nested.span())),
+        };
+        Ok(Self::Alias(
+            list.nested.iter().map(alias).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// eg. `rename_map(TasteSignal = "Taste", FlavoursSignal = "Flavours")`
+    fn make_rename_map(list: &MetaList) -> Result<Self> {
+        let err_rename_map = |span: Span| {
+            Error::new(
+                span,
+                "rename_map must give types new names like `rename_map(TasteSignal = \"Taste\")`",
+            )
+        };
+        if list.nested.is_empty() {
+            return Err(err_rename_map(list.paren_token.span));
+        }
+        let rename = |nested: &NestedMeta| match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) => match (nv.path.get_ident(), &nv.lit) {
+                (Some(name), syn::Lit::Str(s)) => match s.parse::<Ident>() {
+                    Ok(to) => Ok((name.to_string(), to.to_string(), name.span())),
+                    Err(_) => Err(err_rename_map(s.span())),
+                },
+                _ => Err(err_rename_map(nv.span())),
+            },
+            nested => Err(err_rename_map(nested.span())),
+        };
+        Ok(Self::RenameMap(
+            list.nested.iter().map(rename).collect::<Result<_>>()?,
+        ))
+    }
+
+    /// A comma separated list of type names like `"SignalConfig, Settings"`
+    fn make_type_names(lit: &syn::Lit, span: Span, msg: &str) -> Result<Vec<(String, Span)>> {
+        match lit {
+            syn::Lit::Str(s) => s
+                .value()
+                .split(',')
+                .map(|name| match syn::parse_str::<Ident>(name.trim()) {
+                    Ok(name) => Ok((name.to_string(), span)),
+                    Err(_) => Err(Error::new(s.span(), msg)),
+                })
+                .collect(),
+            lit => Err(Error::new(lit.span(), "Only string literals are allowed")),
+        }
+    }
+
+    /// A comma separated list of where predicates like `"T: Serialize, U: Clone"`
+    fn make_bound(lit: &syn::Lit) -> Result<Vec<WherePredicate>> {
+        let parse = Punctuated::<WherePredicate, Token![,]>::parse_terminated;
+        match lit {
+            syn::Lit::Str(s) => match s.parse_with(parse) {
+                Ok(predicates) => Ok(predicates.into_iter().collect()),
+                Err(_) => Err(Error::new(
+                    s.span(),
+                    "The bounds must be like `bound = \"T: Serialize, U: Clone\"`",
+                )),
+            },
+            lit => Err(Error::new(lit.span(), "Only string literals are allowed")),
+        }
+    }
+
+    /// eg. `"pub(crate)"` or `""` for private
+    fn make_vis(lit: &syn::Lit) -> Result<Visibility> {
+        match lit {
+            syn::Lit::Str(s) => s.parse().map_err(|_| {
+                Error::new(
+                    s.span(),
+                    "The visibility must be like `vis = \"pub(crate)\"` or `vis = \"\"` for private",
+                )
+            }),
+            lit => Err(Error::new(lit.span(), "Only string literals are allowed")),
+        }
+    }
+
+    /// eg. `"<T: Clone>"` or `"<T> where T: Clone"`
+    fn make_generics(lit: &syn::Lit) -> Result<Generics> {
+        let parse = |input: syn::parse::ParseStream| {
+            let mut generics: Generics = input.parse()?;
+            generics.where_clause = input.parse()?;
+            Ok(generics)
+        };
+        match lit {
+            syn::Lit::Str(s) => s.parse_with(parse).map_err(|_| {
+                Error::new(
+                    s.span(),
+                    "The generics must be like `generics = \"<T: Clone>\"` or `generics = \"<T> where T: Clone\"`",
+                )
+            }),
+            lit => Err(Error::new(lit.span(), "Only string literals are allowed")),
+        }
+    }
+
+    fn make_case(lit: &syn::Lit) -> Result<Case> {
+        match lit {
+            syn::Lit::Str(s) => Case::from_name(&s.value()).ok_or_else(|| {
+                let names: Vec<String> = Case::ALL
+                    .iter()
+                    .map(|case| format!("`{}`", case.name()))
+                    .collect();
+                Error::new(
+                    s.span(),
+                    format!("The case must be one of {}", names.join(", ")),
+                )
+            }),
+            lit => Err(Error::new(lit.span(), "Only string literals are allowed")),
+        }
+    }
+
+    /// `replace = "Signal" => "Dto"`
+    pub(crate) fn make_replace(name: &Ident, from: &syn::Lit, to: &syn::Lit) -> Result<Self> {
+        match (from, to) {
+            (syn::Lit::Str(from), syn::Lit::Str(to)) => {
+                let pattern = from.value();
+                if pattern
+                    .trim_start_matches('^')
+                    .trim_end_matches('$')
+                    .is_empty()
+                {
+                    Err(Error::new(from.span(), "replace can't be empty"))
+                } else {
+                    Ok(Self::Replace(pattern, to.value(), name.span()))
+                }
+            }
+            (syn::Lit::Str(_), lit) | (lit, _) => Self::err_only_str(lit.span()),
+        }
+    }
+
     fn err_invalid_ident(i: &Ident) -> Result<Self> {
         Err(Error::new(
             i.span(),
@@ -86,10 +329,105 @@ impl AttributeType {
                     Self::RENAME => make(&name, &span, &Self::Rename),
                     Self::ADD_START => make(&name, &span, &Self::AddStart),
                     Self::ADD_END => make(&name, &span, &Self::AddEnd),
+                    Self::ADD_START_ALL => make(&name, &span, &Self::AddStartAll),
+                    Self::ADD_END_ALL => make(&name, &span, &Self::AddEndAll),
                     Self::TRIM_START => make(&name, &span, &Self::TrimStart),
                     Self::TRIM_START_ALL => make(&name, &span, &Self::TrimStartAll),
                     Self::TRIM_END => make(&name, &span, &Self::TrimEnd),
                     Self::TRIM_END_ALL => make(&name, &span, &Self::TrimEndAll),
+                    Self::TRIM_START_REPEAT => make(&name, &span, &Self::TrimStartRepeat),
+                    Self::TRIM_END_REPEAT => make(&name, &span, &Self::TrimEndRepeat),
+                    Self::RENAME_ALL => Ok(Self::RenameAll(Self::make_case(&nv.lit)?, span)),
+                    Self::REPLACE => Err(Error::new(
+                        nv.lit.span(),
+                        "replace needs the text to replace it with like `replace = \"Signal\" => \"Dto\"`",
+                    )),
+                    Self::FIELD_CASE => Ok(Self::FieldRenamer(Renamer::Case(
+                        Self::make_case(&nv.lit)?,
+                        span,
+                    ))),
+                    Self::FIELD_NAME => make(&name, &span, &|v, s| {
+                        Self::FieldRenamer(Renamer::Rename(v, s))
+                    }),
+                    Self::ADD_FIELD_START => make(&name, &span, &|v, s| {
+                        Self::FieldRenamer(Renamer::AddStart(v, s))
+                    }),
+                    Self::ADD_FIELD_END => make(&name, &span, &|v, s| {
+                        Self::FieldRenamer(Renamer::AddEnd(v, s))
+                    }),
+                    Self::TRIM_FIELD_START => make(&name, &span, &|v, s| {
+                        Self::FieldRenamer(Renamer::TrimStart(v, false, s))
+                    }),
+                    Self::TRIM_FIELD_END => make(&name, &span, &|v, s| {
+                        Self::FieldRenamer(Renamer::TrimEnd(v, false, s))
+                    }),
+                    Self::VIS => Ok(Self::Vis(Self::make_vis(&nv.lit)?, span)),
+                    Self::FIELD_VIS => Ok(Self::FieldVis(Self::make_vis(&nv.lit)?, span)),
+                    Self::GENERICS => Ok(Self::Generics(Self::make_generics(&nv.lit)?, span)),
+                    Self::REPR => match &nv.lit {
+                        syn::Lit::Str(s) => match s.parse::<TokenStream>() {
+                            Ok(repr) if !repr.is_empty() => Ok(Self::Repr(repr, span)),
+                            _ => Err(Error::new(
+                                s.span(),
+                                "repr must be like `repr = \"u8\"` or `repr = \"C, u8\"`",
+                            )),
+                        },
+                        lit => Self::err_only_str(lit.span()),
+                    },
+                    Self::DEFAULT => match &nv.lit {
+                        syn::Lit::Str(s) => match s.parse() {
+                            Ok(path) => Ok(Self::Default(path, span)),
+                            Err(_) => Err(Error::new(
+                                s.span(),
+                                "default must be the path to a function",
+                            )),
+                        },
+                        lit => Self::err_only_str(lit.span()),
+                    },
+                    Self::UNWRAP => match &nv.lit {
+                        syn::Lit::Str(s) => match s.parse::<Ident>() {
+                            Ok(ident) => Ok(Self::Unwrap(ident.to_string(), span)),
+                            Err(_) => Err(Error::new(
+                                s.span(),
+                                "unwrap must be the name of a type like `unwrap = \"Validated\"`",
+                            )),
+                        },
+                        lit => Self::err_only_str(lit.span()),
+                    },
+                    Self::RENAME_EXCLUDE => Ok(Self::RenameExclude(Self::make_type_names(
+                        &nv.lit,
+                        span,
+                        "rename_exclude must be the names of types like `rename_exclude = \"SignalConfig, Settings\"`",
+                    )?)),
+                    Self::BOUND => Ok(Self::Bound(Self::make_bound(&nv.lit)?)),
+                    Self::REMOVE_BOUND => Ok(Self::RemoveBound(Self::make_type_names(
+                        &nv.lit,
+                        span,
+                        "remove_bound must be the names of traits like `remove_bound = \"SignalItem, Clone\"`",
+                    )?)),
+                    Self::FOR => Ok(Self::For(Self::make_type_names(
+                        &nv.lit,
+                        span,
+                        "for must be the names of types like `for = \"TasteSignal, FlavoursSignal\"`",
+                    )?)),
+                    Self::MAP => match &nv.lit {
+                        syn::Lit::Str(s) => {
+                            let value = s.value();
+                            let mut split = value.split("=>").map(str::trim);
+                            match (split.next(), split.next(), split.next()) {
+                                (Some(from), Some(to), None) => {
+                                    match (syn::parse_str::<Ident>(from), syn::parse_str(to)) {
+                                        (Ok(from), Ok(to)) => {
+                                            Ok(Self::Map(from.to_string(), to, span))
+                                        }
+                                        _ => Self::err_map(s.span()),
+                                    }
+                                }
+                                _ => Self::err_map(s.span()),
+                            }
+                        }
+                        lit => Self::err_only_str(lit.span()),
+                    },
                     _ => Self::err_invalid_ident(&i),
                 }
             }
@@ -106,15 +444,90 @@ impl AttributeType {
                         Self::REMOVE => Ok(Self::Remove(i.span())),
                         Self::KEEP_RC => Ok(Self::KeepRc(i.span())),
                         Self::KEEP_ARC => Ok(Self::KeepArc(i.span())),
+                        Self::KEEP_BOX => Ok(Self::KeepBox(i.span())),
+                        Self::KEEP_CELL => Ok(Self::KeepCell(i.span())),
+                        Self::KEEP_REFCELL => Ok(Self::KeepRefCell(i.span())),
+                        Self::KEEP_WEAK => Ok(Self::KeepWeak(i.span())),
+                        Self::KEEP_COW => Ok(Self::KeepCow(i.span())),
+                        Self::KEEP_READ_ONLY => Ok(Self::KeepReadOnly(i.span())),
                         Self::HASHMAP => Ok(Self::HashMap(i.span())),
-                        s if s == Self::RENAME || s == Self::ADD_START || s == Self::ADD_END || s == Self::TRIM_START || s == Self::TRIM_END => {
+                        Self::SNAPSHOT => Ok(Self::Snapshot(i.span())),
+                        Self::REBUILD => Ok(Self::Rebuild(i.span())),
+                        Self::APPLY => Ok(Self::Apply(i.span())),
+                        Self::APPLY_NEQ => Ok(Self::ApplyNeq(i.span())),
+                        Self::MUTABLE_VEC => Ok(Self::MutableVec(i.span())),
+                        Self::MUTABLE_MAP => Ok(Self::MutableMap(i.span())),
+                        Self::RC => Ok(Self::Rc(i.span())),
+                        Self::NESTED => Ok(Self::Nested(i.span())),
+                        Self::SKIP_APPLY => Ok(Self::SkipApply(i.span())),
+                        s if s == Self::RENAME || s == Self::ADD_START || s == Self::ADD_END || s == Self::ADD_START_ALL || s == Self::ADD_END_ALL || s == Self::TRIM_START || s == Self::TRIM_END || s == Self::TRIM_START_REPEAT || s == Self::TRIM_END_REPEAT => {
                             Err(Error::new(i.span(), format!("You need to provide a way to rename the struct like `{} = \"NoSignals\"", s)))
                         }
+                        s if s == Self::RENAME_ALL || s == Self::FIELD_CASE => {
+                            Err(Error::new(i.span(), format!("You need to provide the case like `{} = \"snake_case\"`", s)))
+                        }
+                        Self::REPLACE => Err(Error::new(
+                            i.span(),
+                            "You need to provide the text to replace like `replace = \"Signal\" => \"Dto\"`",
+                        )),
+                        s if s == Self::FIELD_NAME || s == Self::ADD_FIELD_START || s == Self::ADD_FIELD_END || s == Self::TRIM_FIELD_START || s == Self::TRIM_FIELD_END => {
+                            Err(Error::new(i.span(), format!("You need to provide a way to rename the field like `{} = \"name\"`", s)))
+                        }
+                        s if s == Self::VIS || s == Self::FIELD_VIS => Err(Error::new(
+                            i.span(),
+                            format!("You need to provide the visibility like `{} = \"pub\"`", s),
+                        )),
+                        Self::REPR => Err(Error::new(
+                            i.span(),
+                            "You need to provide the representation like `repr = \"u8\"`",
+                        )),
+                        Self::GENERICS => Err(Error::new(
+                            i.span(),
+                            "You need to provide the generics like `generics = \"<T: Clone>\"`",
+                        )),
+                        Self::DEFAULT => Err(Error::new(
+                            i.span(),
+                            "You need to provide the function to call like `default = \"new_value\"`",
+                        )),
+                        Self::UNWRAP => Err(Error::new(
+                            i.span(),
+                            "You need to provide the type to unwrap like `unwrap = \"Validated\"`",
+                        )),
+                        Self::MAP => Err(Error::new(
+                            i.span(),
+                            "You need to provide the types to map like `map = \"MyVec => Vec\"`",
+                        )),
+                        Self::RENAME_EXCLUDE => Err(Error::new(
+                            i.span(),
+                            "You need to provide the types to leave as they are like `rename_exclude = \"SignalConfig\"`",
+                        )),
+                        Self::BOUND => Err(Error::new(
+                            i.span(),
+                            "You need to provide the bounds like `bound = \"T: Serialize\"`",
+                        )),
+                        Self::REMOVE_BOUND => Err(Error::new(
+                            i.span(),
+                            "You need to provide the traits to remove like `remove_bound = \"SignalItem\"`",
+                        )),
+                        Self::ALIAS => Err(Error::new(
+                            i.span(),
+                            "You need to provide the aliases like `alias(M = \"Mutable\")`",
+                        )),
+                        Self::FOR => Err(Error::new(
+                            i.span(),
+                            "You need to provide the types to rename like `for = \"TasteSignal\"`",
+                        )),
+                        Self::RENAME_MAP => Err(Error::new(
+                            i.span(),
+                            "You need to provide the new names like `rename_map(TasteSignal = \"Taste\")`",
+                        )),
                         _ => Self::err_invalid_ident(i),
                     },
                     None => Self::err_invalid_option(path.segments[0].ident.span()),
                 },
                 Meta::NameValue(nv) => Self::make_from_meta_name(&nv),
+                Meta::List(l) if l.path.is_ident(Self::ALIAS) => Self::make_alias(l),
+                Meta::List(l) if l.path.is_ident(Self::RENAME_MAP) => Self::make_rename_map(l),
                 Meta::List(l) => Err(Error::new(l.paren_token.span, "Unable to parse attributes")),
             },
             NestedMeta::Lit(l) => {
@@ -142,10 +555,17 @@ pub(crate) enum Renamer {
     Rename(String, Span),
     AddStart(String, Span),
     AddEnd(String, Span),
-    TrimStart(String, Span),
-    TrimStartAll(String, Span),
-    TrimEnd(String, Span),
-    TrimEndAll(String, Span),
+    /// Only the types listed in `for` are renamed
+    AddStartAll(String, Span),
+    AddEndAll(String, Span),
+    /// The flag trims every repeat of the text rather than a single one
+    TrimStart(String, bool, Span),
+    TrimStartAll(String, bool, Span),
+    TrimEnd(String, bool, Span),
+    TrimEndAll(String, bool, Span),
+    Case(Case, Span),
+    /// `^` & `$` only replace at the start or end, otherwise every occurrence is replaced
+    Replace(String, String, Span),
 }
 
 impl Renamer {
@@ -154,91 +574,281 @@ impl Renamer {
             Renamer::Rename(_, s)
             | Renamer::AddStart(_, s)
             | Renamer::AddEnd(_, s)
-            | Renamer::TrimStart(_, s)
-            | Renamer::TrimEnd(_, s) => s,
-            Renamer::TrimStartAll(_, s) => s,
-            Renamer::TrimEndAll(_, s) => s,
+            | Renamer::AddStartAll(_, s)
+            | Renamer::AddEndAll(_, s)
+            | Renamer::TrimStart(_, _, s)
+            | Renamer::TrimEnd(_, _, s) => s,
+            Renamer::TrimStartAll(_, _, s) => s,
+            Renamer::TrimEndAll(_, _, s) => s,
+            Renamer::Case(_, s) | Renamer::Replace(_, _, s) => s,
+        }
+    }
+
+    /// The trims & replaces are only applied to the names they are found in
+    pub fn matches(&self, current: &Ident) -> bool {
+        let name = current.to_string();
+        match self {
+            Self::TrimStart(remove, _, _) | Self::TrimStartAll(remove, _, _) => {
+                name.starts_with(remove.as_str())
+            }
+            Self::TrimEnd(remove, _, _) | Self::TrimEndAll(remove, _, _) => {
+                name.ends_with(remove.as_str())
+            }
+            Self::Replace(from, _, _) => {
+                if let Some(from) = from.strip_prefix('^') {
+                    name.starts_with(from)
+                } else if let Some(from) = from.strip_suffix('$') {
+                    name.ends_with(from)
+                } else {
+                    name.contains(from.as_str())
+                }
+            }
+            _ => true,
+        }
+    }
+
+    /// A field's own trim or replace must be found in at least one of the types of the field
+    pub fn err_no_match(&self, field: &impl quote::ToTokens) -> Option<Error> {
+        let field = field.to_token_stream();
+        let msg = |found: &str, text: &str| {
+            Some(Error::new(
+                *self.span(),
+                format!("field {} has no type that {} {}", field, found, text),
+            ))
+        };
+        match self {
+            Self::TrimStart(remove, _, _) => msg("starts with", remove),
+            Self::TrimEnd(remove, _, _) => msg("ends with", remove),
+            Self::Replace(from, _, _) => msg("contains", from),
+            _ => None,
         }
     }
 
     pub fn make_new_name(&self, current: &Ident, att_location: AttributeLocation) -> Result<Ident> {
+        let location = match att_location {
+            AttributeLocation::Type(_) => "struct",
+            AttributeLocation::Variant => "variant",
+            AttributeLocation::Field(_) => "field",
+        };
         let err_naming = |span: &Span, name: &str, remove: &str, msg: &str| {
-            let location = match att_location {
-                AttributeLocation::Type(_) => "struct",
-                AttributeLocation::Field(_) => "field",
-            };
             Err(Error::new(
                 *span,
                 format!("{} {} does not {} with {}", location, name, msg, remove),
             ))
         };
+        // Trims `remove` once from the start or end, or every repeat of it
+        let trim = |remove: &str, repeat: bool, start: bool| {
+            let name = current.to_string();
+            match (start, repeat) {
+                (true, false) => name.strip_prefix(remove).map(str::to_string),
+                (false, false) => name.strip_suffix(remove).map(str::to_string),
+                (true, true) if name.starts_with(remove) => {
+                    Some(name.trim_start_matches(remove).to_string())
+                }
+                (false, true) if name.ends_with(remove) => {
+                    Some(name.trim_end_matches(remove).to_string())
+                }
+                _ => None,
+            }
+        };
 
-        match self {
-            Self::Rename(new_str, _) => Ok(format_ident!("{}", new_str)),
-            Self::AddStart(pre, _) => Ok(format_ident!("{}{}", pre, current)),
-            Self::AddEnd(post, _) => Ok(format_ident!("{}{}", current, post)),
-            Self::TrimStart(remove, s) => {
+        let new_name = match self {
+            Self::Rename(new_str, _) => new_str.clone(),
+            Self::AddStart(pre, _) | Self::AddStartAll(pre, _) => format!("{}{}", pre, current),
+            Self::AddEnd(post, _) | Self::AddEndAll(post, _) => format!("{}{}", current, post),
+            Self::Case(case, _) => case.convert(&current.to_string()),
+            Self::Replace(from, to, s) => {
                 let name = current.to_string();
-                if name.starts_with(remove) {
-                    Ok(format_ident!("{}", name.trim_start_matches(remove)))
+                let replaced = if let Some(from) = from.strip_prefix('^') {
+                    name.strip_prefix(from)
+                        .map(|rest| format!("{}{}", to, rest))
+                } else if let Some(from) = from.strip_suffix('$') {
+                    name.strip_suffix(from)
+                        .map(|rest| format!("{}{}", rest, to))
+                } else if name.contains(from.as_str()) {
+                    Some(name.replace(from.as_str(), to))
                 } else {
-                    err_naming(s, &name, remove, "start")
+                    None
+                };
+                match replaced {
+                    Some(replaced) => replaced,
+                    None => {
+                        return Err(Error::new(
+                            *s,
+                            format!("{} {} does not contain {}", location, name, from),
+                        ))
+                    }
                 }
             }
-            Self::TrimStartAll(remove, s) => {
-                let name = current.to_string();
-                let id = || Ok(format_ident!("{}", name.trim_start_matches(remove)));
-                match att_location {
-                    AttributeLocation::Type(_) => {
-                        if name.starts_with(remove) {
-                            id()
-                        } else {
-                            err_naming(s, &name, remove, "start")
-                        }
-                    }
-                    AttributeLocation::Field(_) => {
-                        if name.starts_with(remove) {
-                            id()
-                        } else {
-                            Ok(current.clone()) //TODO: Can this be changed to a ref?
-                        }
+            Self::TrimStart(remove, repeat, s) => match trim(remove, *repeat, true) {
+                Some(name) => name,
+                None => return err_naming(s, &current.to_string(), remove, "start"),
+            },
+            Self::TrimEnd(remove, repeat, s) => match trim(remove, *repeat, false) {
+                Some(name) => name,
+                None => return err_naming(s, &current.to_string(), remove, "end"),
+            },
+            Self::TrimStartAll(remove, repeat, s) | Self::TrimEndAll(remove, repeat, s) => {
+                let start = matches!(self, Self::TrimStartAll(_, _, _));
+                match (trim(remove, *repeat, start), att_location) {
+                    (Some(name), _) => name,
+                    (None, AttributeLocation::Type(_) | AttributeLocation::Variant) => {
+                        let msg = if start { "start" } else { "end" };
+                        return err_naming(s, &current.to_string(), remove, msg);
                     }
+                    (None, AttributeLocation::Field(_)) => return Ok(current.clone()),
                 }
             }
-            Self::TrimEnd(remove, s) => {
-                let name = current.to_string();
-                if name.ends_with(remove) {
-                    Ok(format_ident!("{}", name.trim_end_matches(remove)))
-                } else {
-                    err_naming(s, &name, remove, "end")
+        };
+        if new_name.is_empty() {
+            Err(Error::new(
+                *self.span(),
+                format!(
+                    "{} {} would have no name left once renamed",
+                    location, current
+                ),
+            ))
+        } else if syn::parse_str::<Ident>(&new_name).is_err() {
+            Err(Error::new(
+                *self.span(),
+                format!(
+                    "{} {} would be renamed to `{}` which is not a valid name",
+                    location, current, new_name
+                ),
+            ))
+        } else {
+            Ok(format_ident!("{}", new_name))
+        }
+    }
+}
+
+/// The cases `rename_all` & `field_case` can convert to
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Case {
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Lower,
+    Upper,
+}
+
+impl Case {
+    const ALL: [Self; 6] = [
+        Self::Pascal,
+        Self::Camel,
+        Self::Snake,
+        Self::ScreamingSnake,
+        Self::Lower,
+        Self::Upper,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Pascal => "PascalCase",
+            Self::Camel => "camelCase",
+            Self::Snake => "snake_case",
+            Self::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            Self::Lower => "lowercase",
+            Self::Upper => "UPPERCASE",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|case| case.name() == name)
+    }
+
+    /// Words are split on `_` & at each new capital eg. `HTTPServer_state` is `HTTP Server state`
+    fn words(name: &str) -> Vec<String> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '_' {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
                 }
+                continue;
             }
-            Self::TrimEndAll(remove, s) => {
-                let name = current.to_string();
-                let id = || Ok(format_ident!("{}", name.trim_end_matches(remove)));
-                match att_location {
-                    AttributeLocation::Type(_) => {
-                        if name.ends_with(remove) {
-                            id()
-                        } else {
-                            err_naming(s, &name, remove, "end")
-                        }
-                    }
-                    AttributeLocation::Field(_) => {
-                        if name.ends_with(remove) {
-                            id()
-                        } else {
-                            Ok(current.clone()) //Can this be changed to a ref?
-                        }
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = c.is_uppercase()
+                && match prev {
+                    Some(prev) if prev.is_lowercase() || prev.is_numeric() => true,
+                    Some(prev) if prev.is_uppercase() => {
+                        matches!(next, Some(n) if n.is_lowercase())
                     }
-                }
+                    _ => false,
+                };
+            if starts_word && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
             }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+
+    fn convert(&self, name: &str) -> String {
+        let capitalise = |word: &str| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        };
+        let words = Self::words(name);
+        match self {
+            Self::Pascal => words.iter().map(|w| capitalise(w)).collect(),
+            Self::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| match i {
+                    0 => w.to_lowercase(),
+                    _ => capitalise(w),
+                })
+                .collect(),
+            Self::Snake => words
+                .iter()
+                .map(|w| w.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::ScreamingSnake => words
+                .iter()
+                .map(|w| w.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Self::Lower => words.iter().map(|w| w.to_lowercase()).collect(),
+            Self::Upper => words.iter().map(|w| w.to_uppercase()).collect(),
+        }
+    }
+}
+
+/// Which derive the attributes are being read for
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Derive {
+    Designal,
+    Ensignal,
+}
+
+impl Derive {
+    fn attribute_name(&self) -> &'static str {
+        match self {
+            Self::Designal => "designal",
+            Self::Ensignal => "ensignal",
         }
     }
 }
 
 pub(crate) enum AttributeLocation {
     Type(Span),
+    /// A variant of an enum, its renamer is only for the name of the variant
+    Variant,
     Field(Naming),
 }
 
@@ -246,41 +856,203 @@ pub(crate) struct AttributeOptions<'a> {
     pub(crate) remove: Option<Span>,
     pub(crate) ignore: Option<Span>,
     pub(crate) renamer: Option<Renamer>, //TODO: Should this rather be an enum since now mandatory for a struct?
+    /// Renames the field itself rather than its type. The trims are lenient at the container level
+    pub(crate) field_renamer: Option<Renamer>,
     pub(crate) keep_rc: Option<Span>,
     pub(crate) keep_arc: Option<Span>,
+    pub(crate) keep_box: Option<Span>,
+    pub(crate) keep_cell: Option<Span>,
+    pub(crate) keep_refcell: Option<Span>,
+    pub(crate) keep_weak: Option<Span>,
+    pub(crate) keep_cow: Option<Span>,
+    pub(crate) keep_read_only: Option<Span>,
     pub(crate) hashmap: Option<Span>,
+    pub(crate) snapshot: Option<Span>,
+    pub(crate) rebuild: Option<Span>,
+    pub(crate) apply: Option<Span>,
+    pub(crate) apply_neq: Option<Span>,
+    /// The field is left as it is by `apply` & `apply_neq`
+    pub(crate) skip_apply: Option<Span>,
+    pub(crate) default: Option<(Path, Span)>,
+    /// Replaces the visibility of the generated type or field
+    pub(crate) vis: Option<(Visibility, Span)>,
+    /// The visibility of every generated field that doesn't have its own `vis`
+    pub(crate) field_vis: Option<(Visibility, Span)>,
+    /// Used instead of the generics of the original type
+    pub(crate) generics: Option<(Generics, Span)>,
+    /// Used instead of any `#[repr(..)]` on the original type
+    pub(crate) repr: Option<(TokenStream, Span)>,
+    /// Extra where predicates for the generated type
+    pub(crate) bound: Vec<WherePredicate>,
+    /// The idents of traits no longer used as bounds on the generated type
+    pub(crate) remove_bound: Vec<(String, Span)>,
+    pub(crate) mutable_vec: Option<Span>,
+    pub(crate) mutable_map: Option<Span>,
+    pub(crate) rc: Option<Span>,
+    /// The field's types are renamed the same way as the container
+    pub(crate) nested: Option<Span>,
+    /// The idents of custom wrappers to remove like `Mutable`
+    pub(crate) unwrap: Vec<(String, Span)>,
+    /// The idents of custom types to replace with another path
+    pub(crate) map: Vec<(String, Path, Span)>,
+    /// Other names used for the wrappers eg. `M` for `Mutable`
+    pub(crate) alias: Vec<(String, Wrapper, Span)>,
+    /// The idents of types that the renamers leave as they are
+    pub(crate) rename_exclude: Vec<(String, Span)>,
+    /// The idents of the types renamed by `add_start_all` & `add_end_all`
+    pub(crate) rename_for: Vec<(String, Span)>,
+    /// Types given new names whatever the renamer is
+    pub(crate) rename_map: Vec<(String, String, Span)>,
     pub(crate) current_attributes: Vec<&'a Attribute>,
     pub(crate) designal_attributes: (Vec<TokenStream>, bool),
+    /// The ident of the type being designaled, a `Box` that refers to it is always kept
+    pub(crate) container: Option<Ident>,
 }
 
 impl<'a> AttributeOptions<'a> {
-    /// Only want to merge in keep_rc, keep_arc, hashmap, trim_start_all, trim_end_all
-    /// only update when the struct level is_some()
+    /// Only want to merge in the keep_* options, hashmap, mutable_vec, mutable_map, rc,
+    /// trim_start_all, trim_end_all only update when the struct level is_some()
+    /// The struct level unwrap, map & alias are added unless the field already has the type
+    /// A nested field takes the struct level renamer
     // TODO: Do nothing if already some?
-    pub(crate) fn add_type_level_to_field_level(mut self, type_level: &AttributeOptions) -> Self {
+    pub(crate) fn add_type_level_to_field_level(
+        mut self,
+        type_level: &AttributeOptions,
+    ) -> Result<Self> {
+        self.container = type_level.container.clone();
         if type_level.keep_rc.is_some() {
             self.keep_rc = type_level.keep_rc;
         }
         if type_level.keep_arc.is_some() {
             self.keep_arc = type_level.keep_arc;
         }
+        if type_level.keep_box.is_some() {
+            self.keep_box = type_level.keep_box;
+        }
+        if type_level.keep_cell.is_some() {
+            self.keep_cell = type_level.keep_cell;
+        }
+        if type_level.keep_refcell.is_some() {
+            self.keep_refcell = type_level.keep_refcell;
+        }
+        if type_level.keep_weak.is_some() {
+            self.keep_weak = type_level.keep_weak;
+        }
+        if type_level.keep_cow.is_some() {
+            self.keep_cow = type_level.keep_cow;
+        }
+        if type_level.keep_read_only.is_some() {
+            self.keep_read_only = type_level.keep_read_only;
+        }
         if type_level.hashmap.is_some() {
             self.hashmap = type_level.hashmap;
         }
+        if type_level.mutable_vec.is_some() {
+            self.mutable_vec = type_level.mutable_vec;
+        }
+        if type_level.mutable_map.is_some() {
+            self.mutable_map = type_level.mutable_map;
+        }
+        if type_level.rc.is_some() {
+            self.rc = type_level.rc;
+        }
+        for (name, span) in &type_level.unwrap {
+            if !self.has_custom_type(name) {
+                self.unwrap.push((name.clone(), *span));
+            }
+        }
+        for (name, path, span) in &type_level.map {
+            if !self.has_custom_type(name) {
+                self.map.push((name.clone(), path.clone(), *span));
+            }
+        }
+        for (name, wrapper, span) in &type_level.alias {
+            if !self.alias.iter().any(|(n, _, _)| n == name) {
+                self.alias.push((name.clone(), *wrapper, *span));
+            }
+        }
+        // The struct level exclusions are added to the field's own
+        self.rename_exclude
+            .extend(type_level.rename_exclude.iter().cloned());
+        self.rename_for = type_level.rename_for.clone();
+        for (name, to, span) in &type_level.rename_map {
+            if !self.rename_map.iter().any(|(n, _, _)| n == name) {
+                self.rename_map.push((name.clone(), to.clone(), *span));
+            }
+        }
+        if self.field_renamer.is_none() {
+            self.field_renamer = type_level.field_renamer.clone();
+        }
+        if self.vis.is_none() {
+            self.vis = type_level.field_vis.clone();
+        }
         // Struct is only applied if the field has no renamer
         if let (None, Some(renamer)) = (&self.renamer, &type_level.renamer) {
-            match renamer {
-                Renamer::TrimStartAll(_, _) | Renamer::TrimEndAll(_, _) => {
+            match (renamer, self.nested) {
+                (Renamer::Rename(_, _), Some(span)) => {
+                    return Err(Error::new(
+                        span,
+                        "A nested field can't be renamed like the struct since it uses `rename`. Give the field its own renamer instead",
+                    ))
+                }
+                // The field must then have a type that matches the renamer
+                (Renamer::TrimStartAll(v, r, s), Some(_)) => {
+                    self.renamer = Some(Renamer::TrimStart(v.clone(), *r, *s))
+                }
+                (Renamer::TrimEndAll(v, r, s), Some(_)) => {
+                    self.renamer = Some(Renamer::TrimEnd(v.clone(), *r, *s))
+                }
+                (_, Some(_))
+                | (Renamer::TrimStartAll(_, _, _), None)
+                | (Renamer::TrimEndAll(_, _, _), None)
+                | (Renamer::AddStartAll(_, _), None)
+                | (Renamer::AddEndAll(_, _), None) => {
                     self.renamer = type_level.renamer.clone();
                 }
                 _ => (),
             }
         }
-        self
+        Ok(self)
     }
 
-    fn is_designal_att(att: &Attribute) -> bool {
-        att.path.is_ident("designal")
+    /// The variant's options for its fields along with the container's. The variant's own renamer
+    /// is only for its name so the fields are given the container's
+    pub(crate) fn add_type_level_to_variant_level(
+        self,
+        type_level: &AttributeOptions,
+    ) -> Result<Self> {
+        let mut atts = self.add_type_level_to_field_level(type_level)?;
+        atts.renamer = type_level.renamer.clone();
+        atts.field_vis = type_level.field_vis.clone();
+        Ok(atts)
+    }
+
+    /// The type keeps its name when it is listed in `rename_exclude`, or isn't listed in the `for`
+    /// of `add_start_all` & `add_end_all`
+    pub(crate) fn is_excluded(&self, ident: &Ident) -> bool {
+        let is_for = || self.rename_for.iter().any(|(name, _)| ident == name);
+        self.rename_exclude.iter().any(|(name, _)| ident == name)
+            || matches!(
+                self.renamer,
+                Some(Renamer::AddStartAll(_, _) | Renamer::AddEndAll(_, _))
+            ) && !is_for()
+    }
+
+    /// The new name given to the type by `rename_map`
+    pub(crate) fn mapped_name(&self, ident: &Ident) -> Option<Ident> {
+        self.rename_map
+            .iter()
+            .find(|(name, _, _)| ident == name)
+            .map(|(_, to, _)| format_ident!("{}", to))
+    }
+
+    /// The type has already been given to `unwrap` or `map`
+    fn has_custom_type(&self, name: &str) -> bool {
+        self.unwrap.iter().any(|(n, _)| n == name) || self.map.iter().any(|(n, _, _)| n == name)
+    }
+
+    fn is_designal_att(att: &Attribute, derive: Derive) -> bool {
+        att.path.is_ident(derive.attribute_name())
     }
 
     fn get_designal_meta(att: &Attribute) -> Vec<Result<AttributeType>> {
@@ -290,15 +1062,76 @@ impl<'a> AttributeOptions<'a> {
     // TODO: Avoid iterating twice?
     fn get_designal_attributes(
         atts: &[Attribute],
-    ) -> Result<(Vec<AttributeType>, Vec<&Attribute>)> {
-        let (designal, others): (Vec<&Attribute>, Vec<&Attribute>) =
-            atts.iter().partition(|att| Self::is_designal_att(att));
-        let designal: Result<Vec<AttributeType>> = designal
+        derive: Derive,
+    ) -> (Vec<Result<AttributeType>>, Vec<&Attribute>) {
+        let (designal, others): (Vec<&Attribute>, Vec<&Attribute>) = atts
+            .iter()
+            .partition(|att| Self::is_designal_att(att, derive));
+        let designal = designal
             .into_iter()
             .map(Self::get_designal_meta)
             .flatten()
             .collect();
-        Ok((designal?, others))
+        (designal, others)
+    }
+
+    /// Each derive only understands some of the options
+    fn validate_derive(&self, derive: Derive) -> Result<()> {
+        let designal_only = [
+            (self.keep_rc, AttributeType::KEEP_RC),
+            (self.keep_arc, AttributeType::KEEP_ARC),
+            (self.keep_box, AttributeType::KEEP_BOX),
+            (self.keep_cell, AttributeType::KEEP_CELL),
+            (self.keep_refcell, AttributeType::KEEP_REFCELL),
+            (self.keep_weak, AttributeType::KEEP_WEAK),
+            (self.keep_cow, AttributeType::KEEP_COW),
+            (self.keep_read_only, AttributeType::KEEP_READ_ONLY),
+            (self.hashmap, AttributeType::HASHMAP),
+            (self.snapshot, AttributeType::SNAPSHOT),
+            (self.rebuild, AttributeType::REBUILD),
+            (self.apply, AttributeType::APPLY),
+            (self.apply_neq, AttributeType::APPLY_NEQ),
+            (self.skip_apply, AttributeType::SKIP_APPLY),
+            (
+                self.default.as_ref().map(|(_, span)| *span),
+                AttributeType::DEFAULT,
+            ),
+            (
+                self.unwrap.first().map(|(_, span)| *span),
+                AttributeType::UNWRAP,
+            ),
+            (
+                self.map.first().map(|(_, _, span)| *span),
+                AttributeType::MAP,
+            ),
+            (
+                self.alias.first().map(|(_, _, span)| *span),
+                AttributeType::ALIAS,
+            ),
+            (
+                self.repr.as_ref().map(|(_, span)| *span),
+                AttributeType::REPR,
+            ),
+        ];
+        let ensignal_only = [
+            (self.mutable_vec, AttributeType::MUTABLE_VEC),
+            (self.mutable_map, AttributeType::MUTABLE_MAP),
+            (self.rc, AttributeType::RC),
+        ];
+        let (invalid, valid_for) = match derive {
+            Derive::Designal => (ensignal_only.iter(), "Ensignal"),
+            Derive::Ensignal => (designal_only.iter(), "Designal"),
+        };
+        let mut errors = Errors::default();
+        for (span, name) in invalid {
+            if let Some(span) = span {
+                errors.push(Error::new(
+                    *span,
+                    format!("`{}` is only valid when deriving {}", name, valid_for),
+                ));
+            }
+        }
+        errors.finish()
     }
 
     // TODO: Check struct derived against field? eg. if keep_rc etc.
@@ -310,14 +1143,127 @@ impl<'a> AttributeOptions<'a> {
                         span,
                         "Remove is not valid at the container level",
                     ))
+                } else if let Some((_, span)) = self.default {
+                    Err(Error::new(
+                        span,
+                        "Default is not valid at the container level",
+                    ))
                 } else if let Some(span) = self.ignore {
                     Err(Error::new(
                         span,
                         "Ignore is not valid at the container level",
                     ))
+                } else if let Some(span) = self.nested {
+                    Err(Error::new(
+                        span,
+                        "`nested` is only valid at the field level",
+                    ))
+                } else if let Some(span) = self.skip_apply {
+                    Err(Error::new(
+                        span,
+                        "`skip_apply` is only valid at the field level",
+                    ))
+                } else if let (Some((_, span)), false) = (
+                    self.rename_for.first(),
+                    matches!(
+                        self.renamer,
+                        Some(Renamer::AddStartAll(_, _) | Renamer::AddEndAll(_, _))
+                    ),
+                ) {
+                    Err(Error::new(
+                        *span,
+                        "`for` is only valid with `add_start_all` or `add_end_all`",
+                    ))
+                } else if let Some(Renamer::Rename(_, span)) = &self.field_renamer {
+                    Err(Error::new(
+                        *span,
+                        "`field_name` is only valid at the field level",
+                    ))
+                } else if let (
+                    Some(Renamer::AddStartAll(_, span) | Renamer::AddEndAll(_, span)),
+                    true,
+                ) = (&self.renamer, self.rename_for.is_empty())
+                {
+                    Err(Error::new(
+                        *span,
+                        "You need to provide the types to rename like `for = \"TasteSignal\"`",
+                    ))
                 } else if self.renamer.is_none() {
                     //TODO: Add example to error?
-                    Err(Error::new(struct_span, "To use designal a struct must be renamed using rename, add_start, add_end, trim_start, trim_end"))
+                    Err(Error::new(struct_span, "To use designal a struct must be renamed using rename, add_start, add_end, trim_start, trim_end, rename_all, replace"))
+                } else {
+                    Ok(())
+                }
+            }
+            AttributeLocation::Variant => {
+                let container_only = [
+                    (self.snapshot, AttributeType::SNAPSHOT),
+                    (self.rebuild, AttributeType::REBUILD),
+                    (self.apply, AttributeType::APPLY),
+                    (self.apply_neq, AttributeType::APPLY_NEQ),
+                    (
+                        self.field_vis.as_ref().map(|(_, span)| *span),
+                        AttributeType::FIELD_VIS,
+                    ),
+                    (
+                        self.generics.as_ref().map(|(_, span)| *span),
+                        AttributeType::GENERICS,
+                    ),
+                    (
+                        self.repr.as_ref().map(|(_, span)| *span),
+                        AttributeType::REPR,
+                    ),
+                    (
+                        self.bound.first().map(|predicate| predicate.span()),
+                        AttributeType::BOUND,
+                    ),
+                    (
+                        self.remove_bound.first().map(|(_, span)| *span),
+                        AttributeType::REMOVE_BOUND,
+                    ),
+                    (
+                        self.rename_for.first().map(|(_, span)| *span),
+                        AttributeType::FOR,
+                    ),
+                ];
+                if let Some((span, name)) = container_only
+                    .iter()
+                    .find_map(|(span, name)| span.map(|span| (span, name)))
+                {
+                    Err(Error::new(
+                        span,
+                        format!("`{}` is only valid at the container level", name),
+                    ))
+                } else if let Some(span) = self.ignore {
+                    Err(Error::new(span, "Ignore is not valid on a variant"))
+                } else if let Some((_, span)) = self.default {
+                    Err(Error::new(span, "Default is not valid on a variant"))
+                } else if let Some(span) = self.nested {
+                    Err(Error::new(
+                        span,
+                        "`nested` is only valid at the field level",
+                    ))
+                } else if let Some(span) = self.skip_apply {
+                    Err(Error::new(
+                        span,
+                        "`skip_apply` is only valid at the field level",
+                    ))
+                } else if let Some((_, span)) = self.vis {
+                    Err(Error::new(span, "A variant can't be given a visibility"))
+                } else if let (Some(remove), Some(_)) = (self.remove, &self.renamer) {
+                    Err(Error::new(remove, "You have removed and renamed a variant"))
+                } else if let Some(renamer) = &self.renamer {
+                    let name = match renamer {
+                        Renamer::TrimStartAll(_, _, _) => AttributeType::TRIM_START_ALL,
+                        Renamer::TrimEndAll(_, _, _) => AttributeType::TRIM_END_ALL,
+                        Renamer::AddStartAll(_, _) => AttributeType::ADD_START_ALL,
+                        Renamer::AddEndAll(_, _) => AttributeType::ADD_END_ALL,
+                        _ => return Ok(()),
+                    };
+                    Err(Error::new(
+                        *renamer.span(),
+                        format!("`{}` is only valid at the container level", name),
+                    ))
                 } else {
                     Ok(())
                 }
@@ -326,10 +1272,82 @@ impl<'a> AttributeOptions<'a> {
                 let all_but_ignore = self.remove.is_some()
                     || self.renamer.is_some()
                     || self.keep_rc.is_some()
-                    || self.keep_arc.is_some();
+                    || self.keep_arc.is_some()
+                    || self.keep_box.is_some()
+                    || self.keep_cell.is_some()
+                    || self.keep_refcell.is_some()
+                    || self.keep_weak.is_some()
+                    || self.keep_cow.is_some()
+                    || self.keep_read_only.is_some()
+                    || self.mutable_vec.is_some()
+                    || self.mutable_map.is_some()
+                    || self.rc.is_some()
+                    || self.nested.is_some()
+                    || self.skip_apply.is_some()
+                    || !self.unwrap.is_empty()
+                    || !self.map.is_empty()
+                    || !self.alias.is_empty()
+                    || !self.rename_exclude.is_empty()
+                    || !self.rename_map.is_empty();
 
-                if let (Some(remove), Some(_)) = (&self.remove, &self.renamer) {
+                if let Some(span) = self.snapshot {
+                    Err(Error::new(span, "Snapshot is not valid at the field level"))
+                } else if let Some(span) = self.rebuild {
+                    Err(Error::new(span, "Rebuild is not valid at the field level"))
+                } else if let Some((_, span)) = self.field_vis {
+                    Err(Error::new(
+                        span,
+                        "`field_vis` is only valid at the container level, use `vis` instead",
+                    ))
+                } else if let Some((_, span)) = self.generics {
+                    Err(Error::new(
+                        span,
+                        "`generics` is only valid at the container level",
+                    ))
+                } else if let Some((_, span)) = self.repr {
+                    Err(Error::new(
+                        span,
+                        "`repr` is only valid at the container level",
+                    ))
+                } else if let Some(predicate) = self.bound.first() {
+                    Err(Error::new(
+                        predicate.span(),
+                        "`bound` is only valid at the container level",
+                    ))
+                } else if let Some((_, span)) = self.remove_bound.first() {
+                    Err(Error::new(
+                        *span,
+                        "`remove_bound` is only valid at the container level",
+                    ))
+                } else if let Some((_, span)) = self.rename_for.first() {
+                    Err(Error::new(
+                        *span,
+                        "`for` is only valid at the container level",
+                    ))
+                } else if let (Some(span), Some(_)) = (self.nested, &self.renamer) {
+                    Err(Error::new(
+                        span,
+                        "A nested field is renamed like the struct so can't have its own renamer",
+                    ))
+                } else if let Some(span) = self.apply.or(self.apply_neq) {
+                    Err(Error::new(span, "Apply is not valid at the field level"))
+                } else if let (Some((_, span)), None) = (&self.default, &self.remove) {
+                    Err(Error::new(
+                        *span,
+                        "Default is only valid on a removed field",
+                    ))
+                } else if let (Some(span), None) = (self.rc, &self.renamer) {
+                    Err(Error::new(span, "`rc` is only valid on a renamed type"))
+                } else if let (Some(remove), Some(_)) = (
+                    &self.remove,
+                    self.renamer.as_ref().or(self.field_renamer.as_ref()),
+                ) {
                     Err(Error::new(*remove, "You have removed and renamed a field"))
+                } else if let (Some(renamer), true) = (&self.field_renamer, naming.is_unnamed()) {
+                    Err(Error::new(
+                        *renamer.span(),
+                        "Only a named field can have its name changed",
+                    ))
                 } else if self.ignore.is_some() && all_but_ignore {
                     Err(Error::new(
                         self.ignore.unwrap(),
@@ -344,12 +1362,14 @@ impl<'a> AttributeOptions<'a> {
                     let e = |name: &str| {
                         return Err(Error::new(
                             *renamer.span(),
-                            format!("`trim_{}_all` is only valid at the container level", name),
+                            format!("`{}` is only valid at the container level", name),
                         ));
                     };
                     match renamer {
-                        Renamer::TrimStartAll(_, _) => e("start"),
-                        Renamer::TrimEndAll(_, _) => e("end"),
+                        Renamer::TrimStartAll(_, _, _) => e(AttributeType::TRIM_START_ALL),
+                        Renamer::TrimEndAll(_, _, _) => e(AttributeType::TRIM_END_ALL),
+                        Renamer::AddStartAll(_, _) => e(AttributeType::ADD_START_ALL),
+                        Renamer::AddEndAll(_, _) => e(AttributeType::ADD_END_ALL),
                         _ => Ok(()),
                     }
                 } else {
@@ -359,8 +1379,56 @@ impl<'a> AttributeOptions<'a> {
         }
     }
 
-    pub(crate) fn new(atts: &'a [Attribute], att_location: AttributeLocation) -> Result<Self> {
-        let (d_atts, current_attributes) = Self::get_designal_attributes(atts)?;
+    /// When the container's attributes have errors the fields are still checked so that
+    /// everything can be fixed at once
+    pub(crate) fn new_container(input: &'a DeriveInput, derive: Derive) -> Result<Self> {
+        let location = AttributeLocation::Type(input.ident.span());
+        let mut errors = match Self::new(&input.attrs, location, derive) {
+            Ok(atts) => {
+                return Ok(Self {
+                    container: Some(input.ident.clone()),
+                    ..atts
+                })
+            }
+            Err(e) => e,
+        };
+        let fields: Vec<(&Field, Naming)> = match &input.data {
+            Data::Struct(data) => {
+                let naming = Naming::of(&data.fields);
+                data.fields.iter().map(|field| (field, naming)).collect()
+            }
+            Data::Enum(data) => data
+                .variants
+                .iter()
+                .flat_map(|variant| {
+                    let naming = Naming::of(&variant.fields);
+                    variant.fields.iter().map(move |field| (field, naming))
+                })
+                .collect(),
+            Data::Union(_) => Vec::new(),
+        };
+        for (field, naming) in fields {
+            if let Err(e) = Self::new(&field.attrs, AttributeLocation::Field(naming), derive) {
+                errors.combine(e);
+            }
+        }
+        if let Data::Enum(data) = &input.data {
+            for variant in &data.variants {
+                if let Err(e) = Self::new(&variant.attrs, AttributeLocation::Variant, derive) {
+                    errors.combine(e);
+                }
+            }
+        }
+        Err(errors)
+    }
+
+    pub(crate) fn new(
+        atts: &'a [Attribute],
+        att_location: AttributeLocation,
+        derive: Derive,
+    ) -> Result<Self> {
+        let (d_atts, current_attributes) = Self::get_designal_attributes(atts, derive);
+        let mut errors = Errors::default();
         let mut ignore: Option<Span> = None;
         let mut remove: Option<Span> = None;
         let mut rename: Option<Renamer> = None;
@@ -368,9 +1436,40 @@ impl<'a> AttributeOptions<'a> {
         let mut add_start: Option<Renamer> = None;
         let mut trim_start: Option<Renamer> = None;
         let mut trim_end: Option<Renamer> = None;
+        let mut rename_all: Option<Renamer> = None;
+        let mut replace: Option<Renamer> = None;
+        let mut field_renamers: Vec<Renamer> = Vec::new();
         let mut keep_rc: Option<Span> = None;
         let mut keep_arc: Option<Span> = None;
+        let mut keep_box: Option<Span> = None;
+        let mut keep_cell: Option<Span> = None;
+        let mut keep_refcell: Option<Span> = None;
+        let mut keep_weak: Option<Span> = None;
+        let mut keep_cow: Option<Span> = None;
+        let mut keep_read_only: Option<Span> = None;
         let mut hashmap: Option<Span> = None;
+        let mut snapshot: Option<Span> = None;
+        let mut rebuild: Option<Span> = None;
+        let mut apply: Option<Span> = None;
+        let mut apply_neq: Option<Span> = None;
+        let mut skip_apply: Option<Span> = None;
+        let mut default: Option<(Path, Span)> = None;
+        let mut vis: Option<(Visibility, Span)> = None;
+        let mut field_vis: Option<(Visibility, Span)> = None;
+        let mut generics: Option<(Generics, Span)> = None;
+        let mut repr: Option<(TokenStream, Span)> = None;
+        let mut bound: Vec<WherePredicate> = Vec::new();
+        let mut remove_bound: Vec<(String, Span)> = Vec::new();
+        let mut mutable_vec: Option<Span> = None;
+        let mut mutable_map: Option<Span> = None;
+        let mut rc: Option<Span> = None;
+        let mut nested: Option<Span> = None;
+        let mut unwrap: Vec<(String, Span)> = Vec::new();
+        let mut map: Vec<(String, Path, Span)> = Vec::new();
+        let mut alias: Vec<(String, Wrapper, Span)> = Vec::new();
+        let mut rename_exclude: Vec<(String, Span)> = Vec::new();
+        let mut rename_for: Vec<(String, Span)> = Vec::new();
+        let mut rename_map: Vec<(String, String, Span)> = Vec::new();
         let mut designal_attributes: (Vec<TokenStream>, bool) = (Vec::new(), false);
 
         let set_span = |existing: &mut Option<Span>, name: &str, new_value: &Span| match existing {
@@ -396,72 +1495,281 @@ impl<'a> AttributeOptions<'a> {
                 }
             };
 
+        let check_custom_type =
+            |unwrap: &[(String, Span)], map: &[(String, Path, Span)], name: &str, span: Span| {
+                if unwrap.iter().any(|(n, _)| n == name) || map.iter().any(|(n, _, _)| n == name) {
+                    Err(Error::new(
+                        span,
+                        format!("`{}` is already unwrapped or mapped", name),
+                    ))
+                } else {
+                    Ok(())
+                }
+            };
+
         for att in d_atts {
-            match att {
-                AttributeType::Ignore(span) => set_span(&mut ignore, "ignore", &span)?,
-                AttributeType::Remove(span) => set_span(&mut remove, "remove", &span)?,
+            let att = match att {
+                Ok(att) => att,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            let result = match att {
+                AttributeType::Ignore(span) => set_span(&mut ignore, "ignore", &span),
+                AttributeType::Remove(span) => set_span(&mut remove, "remove", &span),
                 AttributeType::Rename(name, span) => {
-                    set_renamer(&mut rename, "rename", Renamer::Rename(name, span))?
+                    set_renamer(&mut rename, "rename", Renamer::Rename(name, span))
                 }
                 AttributeType::AddStart(name, span) => {
-                    set_renamer(&mut add_start, "prefix", Renamer::AddStart(name, span))?
+                    set_renamer(&mut add_start, "prefix", Renamer::AddStart(name, span))
                 }
                 AttributeType::AddEnd(name, span) => {
-                    set_renamer(&mut add_end, "postfix", Renamer::AddEnd(name, span))?
+                    set_renamer(&mut add_end, "postfix", Renamer::AddEnd(name, span))
+                }
+                AttributeType::AddStartAll(name, span) => {
+                    set_renamer(&mut add_start, "prefix", Renamer::AddStartAll(name, span))
+                }
+                AttributeType::AddEndAll(name, span) => {
+                    set_renamer(&mut add_end, "postfix", Renamer::AddEndAll(name, span))
+                }
+                AttributeType::For(names) => {
+                    rename_for.extend(names);
+                    Ok(())
+                }
+                AttributeType::RenameMap(names) => {
+                    for (name, to, span) in names {
+                        if rename_map.iter().any(|(n, _, _)| *n == name) {
+                            errors.push(Error::new(
+                                span,
+                                format!("`{}` is already given a new name", name),
+                            ));
+                        } else {
+                            rename_map.push((name, to, span))
+                        }
+                    }
+                    Ok(())
                 }
                 AttributeType::TrimStart(name, span) => set_renamer(
                     &mut trim_start,
                     "trim_start",
-                    Renamer::TrimStart(name, span),
-                )?,
+                    Renamer::TrimStart(name, false, span),
+                ),
                 AttributeType::TrimStartAll(name, span) => set_renamer(
                     &mut trim_start,
                     "trim_start",
-                    Renamer::TrimStartAll(name, span),
-                )?,
-                AttributeType::TrimEnd(name, span) => {
-                    set_renamer(&mut trim_end, "trim_end", Renamer::TrimEnd(name, span))?
-                }
-                AttributeType::TrimEndAll(name, span) => {
-                    set_renamer(&mut trim_end, "trim_end", Renamer::TrimEndAll(name, span))?
-                }
-                AttributeType::KeepRc(span) => set_span(&mut keep_rc, "keep_rc", &span)?,
-                AttributeType::KeepArc(span) => set_span(&mut keep_arc, "keep_arc", &span)?,
-                AttributeType::HashMap(span) => set_span(&mut hashmap, "hashmap", &span)?,
-                AttributeType::Attributes(v) => designal_attributes.0.push(v),
+                    Renamer::TrimStartAll(name, false, span),
+                ),
+                AttributeType::TrimStartRepeat(name, span) => set_renamer(
+                    &mut trim_start,
+                    "trim_start",
+                    Renamer::TrimStart(name, true, span),
+                ),
+                AttributeType::TrimEnd(name, span) => set_renamer(
+                    &mut trim_end,
+                    "trim_end",
+                    Renamer::TrimEnd(name, false, span),
+                ),
+                AttributeType::TrimEndAll(name, span) => set_renamer(
+                    &mut trim_end,
+                    "trim_end",
+                    Renamer::TrimEndAll(name, false, span),
+                ),
+                AttributeType::TrimEndRepeat(name, span) => set_renamer(
+                    &mut trim_end,
+                    "trim_end",
+                    Renamer::TrimEnd(name, true, span),
+                ),
+                AttributeType::RenameAll(case, span) => {
+                    set_renamer(&mut rename_all, "rename_all", Renamer::Case(case, span))
+                }
+                AttributeType::Replace(from, to, span) => {
+                    set_renamer(&mut replace, "replace", Renamer::Replace(from, to, span))
+                }
+                AttributeType::FieldRenamer(renamer) => {
+                    field_renamers.push(renamer);
+                    Ok(())
+                }
+                AttributeType::KeepRc(span) => set_span(&mut keep_rc, "keep_rc", &span),
+                AttributeType::KeepArc(span) => set_span(&mut keep_arc, "keep_arc", &span),
+                AttributeType::KeepBox(span) => set_span(&mut keep_box, "keep_box", &span),
+                AttributeType::KeepCell(span) => set_span(&mut keep_cell, "keep_cell", &span),
+                AttributeType::KeepRefCell(span) => {
+                    set_span(&mut keep_refcell, "keep_refcell", &span)
+                }
+                AttributeType::KeepWeak(span) => set_span(&mut keep_weak, "keep_weak", &span),
+                AttributeType::KeepCow(span) => set_span(&mut keep_cow, "keep_cow", &span),
+                AttributeType::KeepReadOnly(span) => {
+                    set_span(&mut keep_read_only, "keep_read_only", &span)
+                }
+                AttributeType::HashMap(span) => set_span(&mut hashmap, "hashmap", &span),
+                AttributeType::Snapshot(span) => set_span(&mut snapshot, "snapshot", &span),
+                AttributeType::Rebuild(span) => set_span(&mut rebuild, "rebuild", &span),
+                AttributeType::Apply(span) => set_span(&mut apply, "apply", &span),
+                AttributeType::ApplyNeq(span) => set_span(&mut apply_neq, "apply_neq", &span),
+                AttributeType::SkipApply(span) => set_span(&mut skip_apply, "skip_apply", &span),
+                AttributeType::Default(path, span) => match default {
+                    Some(_) => Err(Error::new(span, "You should only `default` once")),
+                    None => {
+                        default = Some((path, span));
+                        Ok(())
+                    }
+                },
+                AttributeType::Vis(v, span) => match vis {
+                    Some(_) => Err(Error::new(span, "You should only `vis` once")),
+                    None => {
+                        vis = Some((v, span));
+                        Ok(())
+                    }
+                },
+                AttributeType::FieldVis(v, span) => match field_vis {
+                    Some(_) => Err(Error::new(span, "You should only `field_vis` once")),
+                    None => {
+                        field_vis = Some((v, span));
+                        Ok(())
+                    }
+                },
+                AttributeType::Repr(r, span) => match repr {
+                    Some(_) => Err(Error::new(span, "You should only `repr` once")),
+                    None => {
+                        repr = Some((r, span));
+                        Ok(())
+                    }
+                },
+                AttributeType::Generics(g, span) => match generics {
+                    Some(_) => Err(Error::new(span, "You should only `generics` once")),
+                    None => {
+                        generics = Some((g, span));
+                        Ok(())
+                    }
+                },
+                AttributeType::MutableVec(span) => set_span(&mut mutable_vec, "mutable_vec", &span),
+                AttributeType::MutableMap(span) => set_span(&mut mutable_map, "mutable_map", &span),
+                AttributeType::Rc(span) => set_span(&mut rc, "rc", &span),
+                AttributeType::Nested(span) => set_span(&mut nested, "nested", &span),
+                AttributeType::Unwrap(name, span) => {
+                    check_custom_type(&unwrap, &map, &name, span).map(|_| unwrap.push((name, span)))
+                }
+                AttributeType::Map(name, path, span) => {
+                    check_custom_type(&unwrap, &map, &name, span)
+                        .map(|_| map.push((name, path, span)))
+                }
+                AttributeType::Alias(aliases) => {
+                    for (name, wrapper, span) in aliases {
+                        if alias.iter().any(|(n, _, _)| *n == name) {
+                            errors
+                                .push(Error::new(span, format!("`{}` is already an alias", name)));
+                        } else {
+                            alias.push((name, wrapper, span))
+                        }
+                    }
+                    Ok(())
+                }
+                AttributeType::RenameExclude(names) => {
+                    rename_exclude.extend(names);
+                    Ok(())
+                }
+                AttributeType::Bound(predicates) => {
+                    bound.extend(predicates);
+                    Ok(())
+                }
+                AttributeType::RemoveBound(names) => {
+                    remove_bound.extend(names);
+                    Ok(())
+                }
+                AttributeType::Attributes(v) => {
+                    designal_attributes.0.push(v);
+                    Ok(())
+                }
                 AttributeType::AttributesReplace(v) => {
                     designal_attributes.0.push(v);
                     designal_attributes.1 = true;
+                    Ok(())
                 }
-            }
+            };
+            errors.check(result);
         }
 
         let renamer = {
-            let all = [rename, add_start, add_end, trim_start, trim_end];
+            let all = [
+                rename, add_start, add_end, trim_start, trim_end, rename_all, replace,
+            ];
             let renamer: Vec<&Renamer> = all.iter().filter_map(|v| v.as_ref()).collect();
             if renamer.len() == 1 {
                 Some(renamer[0].to_owned()) //TODO: remove the clone?
             } else {
-                match renamer.last() {
-                    Some(&v) => return Err(Error::new(
+                if let Some(&v) = renamer.last() {
+                    errors.push(Error::new(
                         *v.span(),
-                        "You can only do one of rename, add_start, add_end, trim_start, trim_end",
-                    )),
-                    None => None,
+                        "You can only do one of rename, add_start, add_end, trim_start, trim_end, rename_all, replace",
+                    ));
                 }
+                None
             }
         };
 
+        if let [_, .., last] = field_renamers.as_slice() {
+            errors.push(Error::new(
+                *last.span(),
+                "You can only do one of field_name, field_case, add_field_start, add_field_end, trim_field_start, trim_field_end",
+            ));
+        }
+        // At the container & variant level the trims are applied to the fields that match them
+        let field_renamer = match (field_renamers.pop(), &att_location) {
+            (
+                Some(Renamer::TrimStart(v, r, s)),
+                AttributeLocation::Type(_) | AttributeLocation::Variant,
+            ) => Some(Renamer::TrimStartAll(v, r, s)),
+            (
+                Some(Renamer::TrimEnd(v, r, s)),
+                AttributeLocation::Type(_) | AttributeLocation::Variant,
+            ) => Some(Renamer::TrimEndAll(v, r, s)),
+            (renamer, _) => renamer,
+        };
+
         let atts = Self {
             ignore,
             remove,
             renamer,
+            field_renamer,
             keep_rc,
             keep_arc,
+            keep_box,
+            keep_cell,
+            keep_refcell,
+            keep_weak,
+            keep_cow,
+            keep_read_only,
             hashmap,
+            snapshot,
+            rebuild,
+            apply,
+            apply_neq,
+            skip_apply,
+            default,
+            vis,
+            field_vis,
+            generics,
+            repr,
+            bound,
+            remove_bound,
+            mutable_vec,
+            mutable_map,
+            rc,
+            nested,
+            unwrap,
+            map,
+            alias,
+            rename_exclude,
+            rename_for,
+            rename_map,
             current_attributes,
             designal_attributes,
+            container: None,
         };
+        errors.check(atts.validate_derive(derive));
+        // The other checks would only add noise when an option couldn't be read
+        errors.finish()?;
         atts.validate(att_location)?;
         Ok(atts)
     }
@@ -1,10 +1,14 @@
-// TODO: Handle generics / lifetime / where if removed
 use crate::attributes::*;
-use proc_macro2::TokenStream;
-use quote::quote;
+use crate::convert;
+use crate::errors::{CollectAll, Errors};
+use crate::generics;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
-    spanned::Spanned, AngleBracketedGenericArguments, DataEnum, DataStruct, DeriveInput, Error,
-    Field, Ident, Path, PathArguments, Result, Variant,
+    punctuated::Punctuated, spanned::Spanned, DataEnum, DataStruct, DeriveInput, Error, Field,
+    Fields, GenericArgument, GenericParam, Generics, Ident, Member, Path, PathArguments,
+    PathSegment, Result, Token, Type, TypeArray, TypeParamBound, TypeReference, TypeSlice, Variant,
+    Visibility, WherePredicate,
 };
 
 #[derive(Copy, Clone)]
@@ -14,119 +18,736 @@ pub(crate) enum Naming {
 }
 
 impl Naming {
+    /// The fields of a tuple struct or tuple variant are unnamed
+    pub(crate) fn of(fields: &Fields) -> Self {
+        match fields {
+            Fields::Named(_) => Self::Named,
+            Fields::Unnamed(_) | Fields::Unit => Self::Unnamed,
+        }
+    }
+
     pub(crate) fn is_unnamed(&self) -> bool {
         matches!(self, Self::Unnamed)
     }
 }
 
+/// The type of a field once designal has worked out which wrappers to remove. The signal side
+/// paths are kept so that conversions between the two types can be generated.
+pub(crate) enum FieldType {
+    /// `Mutable<T>` -> `T`
+    Mutable(Path, Box<FieldType>),
+    /// `MutableVec<T>` -> `Vec<T>`
+    MutableVec(Path, Box<FieldType>),
+    /// `MutableBTreeMap<K, V>` -> `BTreeMap<K, V>` or `HashMap<K, V>`
+    MutableBTreeMap {
+        path: Path,
+        key: Box<FieldType>,
+        value: Box<FieldType>,
+        hashmap: bool,
+    },
+    /// `MutableBTreeMap<K, ()>` -> `BTreeSet<K>` or `HashSet<K>`
+    MutableBTreeSet {
+        path: Path,
+        key: Box<FieldType>,
+        hashmap: bool,
+    },
+    /// `Rc<T>`, `Arc<T>` or `Box<T>` -> `T`
+    Pointer(Path, Box<FieldType>),
+    /// `Cell<T>` -> `T`
+    Cell(Path, Box<FieldType>),
+    /// `RefCell<T>` -> `T`
+    RefCell(Path, Box<FieldType>),
+    /// `ReadOnlyMutable<T>` -> `T`
+    ReadOnlyMutable(Box<FieldType>),
+    /// `Weak<T>` -> `Option<T>` since the value may have been dropped
+    Weak(Path, Box<FieldType>),
+    /// `Cow<'a, B>` -> the owned type of `B` eg. `Cow<'a, str>` -> `String`
+    Cow(Path, TokenStream),
+    /// Any other generic type where designal changes some of the arguments eg.
+    /// `Option<Mutable<T>>` -> `Option<T>`
+    Generic {
+        signal: Path,
+        /// The designaled path up to and including the (possibly renamed) ident
+        ident: TokenStream,
+        renamed: bool,
+        args: Vec<FieldType>,
+    },
+    /// `&'a Mutable<T>` -> `&'a T`
+    Reference(TypeReference, Box<FieldType>),
+    /// `(Mutable<A>, Mutable<B>)` -> `(A, B)`
+    Tuple(Vec<FieldType>),
+    /// `[Mutable<T>; N]` -> `[T; N]`
+    Array(TypeArray, Box<FieldType>),
+    /// `[Mutable<T>]` -> `[T]`
+    Slice(TypeSlice, Box<FieldType>),
+    /// A custom wrapper from `unwrap = "Validated"`: `Validated<T>` -> `T`
+    Unwrapped(Path, Box<FieldType>),
+    /// A custom type from `map = "MyVec => Vec"`: `MyVec<T>` -> `Vec<T>`
+    Mapped {
+        signal: Path,
+        to: Path,
+        args: Vec<FieldType>,
+    },
+    /// Any other type: it is left as is unless a renamer changes its name
+    Other {
+        signal: TokenStream,
+        renamed: Option<TokenStream>,
+    },
+}
+
+impl ToTokens for FieldType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ty = match self {
+            Self::Mutable(_, inner)
+            | Self::Pointer(_, inner)
+            | Self::Cell(_, inner)
+            | Self::RefCell(_, inner)
+            | Self::ReadOnlyMutable(inner)
+            | Self::Unwrapped(_, inner) => quote! { #inner },
+            Self::Weak(_, inner) => quote! { Option<#inner> },
+            Self::Cow(_, owned) => quote! { #owned },
+            Self::MutableVec(_, inner) => quote! { Vec<#inner> },
+            // For hygine this must always be the full path
+            Self::MutableBTreeMap {
+                key,
+                value,
+                hashmap,
+                ..
+            } => match hashmap {
+                true => quote! { std::collections::HashMap<#key, #value> },
+                false => quote! { std::collections::BTreeMap<#key, #value> },
+            },
+            Self::MutableBTreeSet { key, hashmap, .. } => match hashmap {
+                true => quote! { std::collections::HashSet<#key> },
+                false => quote! { std::collections::BTreeSet<#key> },
+            },
+            Self::Generic { ident, args, .. } => quote! { #ident<#(#args),*> },
+            Self::Reference(signal, inner) => {
+                let lifetime = &signal.lifetime;
+                let mutability = &signal.mutability;
+                quote! { &#lifetime #mutability #inner }
+            }
+            // A single element tuple needs the trailing comma
+            Self::Tuple(elems) if elems.len() == 1 => quote! { (#(#elems),*,) },
+            Self::Tuple(elems) => quote! { (#(#elems),*) },
+            Self::Array(signal, inner) => {
+                let len = &signal.len;
+                quote! { [#inner; #len] }
+            }
+            Self::Slice(_, inner) => quote! { [#inner] },
+            Self::Mapped { to, args, .. } if args.is_empty() => quote! { #to },
+            Self::Mapped { to, args, .. } => quote! { #to<#(#args),*> },
+            Self::Other { signal, renamed } => match renamed {
+                Some(renamed) => quote! { #renamed },
+                None => quote! { #signal },
+            },
+        };
+        tokens.extend(ty)
+    }
+}
+
+impl FieldType {
+    /// Designal leaves the type exactly as it is
+    pub(crate) fn is_unchanged(&self) -> bool {
+        matches!(self, Self::Other { renamed: None, .. })
+    }
+
+    /// A renamer has changed the name of this or any type inside it
+    pub(crate) fn is_renamed(&self) -> bool {
+        match self {
+            Self::Mutable(_, inner)
+            | Self::MutableVec(_, inner)
+            | Self::Pointer(_, inner)
+            | Self::Cell(_, inner)
+            | Self::RefCell(_, inner)
+            | Self::ReadOnlyMutable(inner)
+            | Self::Weak(_, inner)
+            | Self::Reference(_, inner)
+            | Self::Array(_, inner)
+            | Self::Slice(_, inner)
+            | Self::Unwrapped(_, inner) => inner.is_renamed(),
+            Self::MutableBTreeMap { key, value, .. } => key.is_renamed() || value.is_renamed(),
+            Self::MutableBTreeSet { key, .. } => key.is_renamed(),
+            Self::Generic { renamed, args, .. } => {
+                *renamed || args.iter().any(FieldType::is_renamed)
+            }
+            Self::Tuple(args) | Self::Mapped { args, .. } => args.iter().any(FieldType::is_renamed),
+            Self::Other { renamed, .. } => renamed.is_some(),
+            Self::Cow(..) => false,
+        }
+    }
+}
+
+/// The wrappers designal knows how to remove
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Wrapper {
+    Mutable,
+    MutableVec,
+    MutableBTreeMap,
+    ReadOnlyMutable,
+    Rc,
+    Arc,
+    Box,
+    Cell,
+    RefCell,
+    Weak,
+    Cow,
+}
+
+impl Wrapper {
+    pub(crate) const ALL: [Self; 11] = [
+        Self::Mutable,
+        Self::MutableVec,
+        Self::MutableBTreeMap,
+        Self::ReadOnlyMutable,
+        Self::Rc,
+        Self::Arc,
+        Self::Box,
+        Self::Cell,
+        Self::RefCell,
+        Self::Weak,
+        Self::Cow,
+    ];
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Mutable => "Mutable",
+            Self::MutableVec => "MutableVec",
+            Self::MutableBTreeMap => "MutableBTreeMap",
+            Self::ReadOnlyMutable => "ReadOnlyMutable",
+            Self::Rc => "Rc",
+            Self::Arc => "Arc",
+            Self::Box => "Box",
+            Self::Cell => "Cell",
+            Self::RefCell => "RefCell",
+            Self::Weak => "Weak",
+            Self::Cow => "Cow",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|w| w.name() == name)
+    }
+
+    /// The modules the wrapper can be used from. A path to the wrapper must be the end of one of these
+    fn modules(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mutable | Self::ReadOnlyMutable => &["futures_signals::signal"],
+            Self::MutableVec => &["futures_signals::signal_vec"],
+            Self::MutableBTreeMap => &["futures_signals::signal_map"],
+            Self::Rc => &["std::rc", "alloc::rc"],
+            Self::Arc => &["std::sync", "alloc::sync"],
+            Self::Box => &["std::boxed", "alloc::boxed"],
+            Self::Cell | Self::RefCell => &["std::cell", "core::cell"],
+            Self::Weak => &["std::rc", "std::sync", "alloc::rc", "alloc::sync"],
+            Self::Cow => &["std::borrow", "alloc::borrow"],
+        }
+    }
+
+    fn is_kept(&self, segment: &PathSegment, atts: &AttributeOptions) -> bool {
+        match self {
+            Self::Mutable | Self::MutableVec | Self::MutableBTreeMap => false,
+            Self::ReadOnlyMutable => atts.keep_read_only.is_some(),
+            Self::Rc => atts.keep_rc.is_some(),
+            Self::Arc => atts.keep_arc.is_some(),
+            Self::Box => {
+                atts.keep_box.is_some()
+                    || matches!(type_arguments(segment).as_slice(), [inner] if needs_box(inner, atts))
+            }
+            Self::Cell => atts.keep_cell.is_some(),
+            Self::RefCell => atts.keep_refcell.is_some(),
+            Self::Weak => atts.keep_weak.is_some(),
+            Self::Cow => atts.keep_cow.is_some(),
+        }
+    }
+
+    fn expected_arguments(&self) -> &'static str {
+        match self {
+            Self::MutableBTreeMap => "two type arguments",
+            _ => "a single type argument",
+        }
+    }
+
+    /// A single ident may have been imported from anywhere so is trusted, an alias always is
+    /// the wrapper & a longer path must lead to where the wrapper is defined
+    fn find(path: &Path, atts: &AttributeOptions) -> Option<Self> {
+        let segment = path.segments.last()?;
+        if path.leading_colon.is_none() && path.segments.len() == 1 {
+            if let Some((_, wrapper, _)) = atts.alias.iter().find(|(n, _, _)| segment.ident == n) {
+                return Some(*wrapper);
+            }
+        }
+        let wrapper = Self::from_name(&segment.ident.to_string())?;
+        if path.leading_colon.is_none() && path.segments.len() == 1 {
+            return Some(wrapper);
+        }
+        let segments: Vec<Ident> = path.segments.iter().map(|s| s.ident.clone()).collect();
+        let is_known = wrapper.modules().iter().any(|module| {
+            let known: Vec<&str> = module.split("::").chain(Some(wrapper.name())).collect();
+            let matches = |start: usize| known[start..].iter().zip(&segments).all(|(k, s)| s == k);
+            match path.leading_colon {
+                Some(_) => known.len() == segments.len() && matches(0),
+                None => known.len() >= segments.len() && matches(known.len() - segments.len()),
+            }
+        });
+        match is_known {
+            true => Some(wrapper),
+            false => None,
+        }
+    }
+}
+
+/// A `Box` is needed when its type is unsized or would otherwise make the designaled type recursive
+fn needs_box(ty: &Type, atts: &AttributeOptions) -> bool {
+    fn mentions(tokens: TokenStream, name: Option<&Ident>) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(i) => i == "Self" || name == Some(&i),
+            TokenTree::Group(g) => mentions(g.stream(), name),
+            _ => false,
+        })
+    }
+    match ty {
+        Type::TraitObject(_) | Type::Slice(_) => true,
+        Type::Path(p) if p.qself.is_none() && p.path.is_ident("str") => true,
+        Type::Paren(p) => needs_box(&p.elem, atts),
+        Type::Group(g) => needs_box(&g.elem, atts),
+        ty => mentions(quote! { #ty }, atts.container.as_ref()),
+    }
+}
+
+/// The renamers that don't look for text in the name need a single type in the field to rename
+fn check_rename_target(ty: &Type, member: &Member, atts: &AttributeOptions) -> Result<()> {
+    let renamer = match &atts.renamer {
+        Some(
+            renamer @ (Renamer::Rename(..)
+            | Renamer::AddStart(..)
+            | Renamer::AddEnd(..)
+            | Renamer::Case(..)),
+        ) => renamer,
+        _ => return Ok(()),
+    };
+    let mut targets = Vec::new();
+    rename_targets(ty, atts, &mut targets);
+    match targets.as_slice() {
+        [] => Err(Error::new(
+            atts.nested.unwrap_or(*renamer.span()),
+            format!(
+                "field {} has no type to rename, std types & primitives are left as they are",
+                member.to_token_stream()
+            ),
+        )),
+        [_] => Ok(()),
+        [first, second, ..] => Err(Error::new(
+            second.span(),
+            format!(
+                "Only one type in a field can be renamed but `{}` & `{}` both would be. Use `rename_map` or `rename_exclude` to choose",
+                first, second
+            ),
+        )),
+    }
+}
+
+/// How a renamer is applied to the type being mapped. The trims & replaces are applied to every
+/// type they match at any depth, the others only to the type the field is declared as.
+#[derive(Copy, Clone)]
+enum Renaming {
+    /// The type declared on the field, once any wrappers & std containers are removed
+    Field,
+    /// A generic type on the field: if it isn't renamed it is tried on the arguments instead
+    Generic,
+    /// The arguments of a renamed generic type: only the trims & replaces are applied
+    Argument,
+}
+
+/// These are kept on the designaled type, but any wrappers in their arguments are removed
+const CONTAINERS: [&str; 9] = [
+    "Option", "Result", "Box", "Vec", "VecDeque", "BTreeMap", "HashMap", "BTreeSet", "HashSet",
+];
+
+/// The primitives & other std types that are never renamed
+const STD_TYPES: [&str; 18] = [
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64", "String",
+];
+
+/// A std type is only looked into for its arguments, as are the wrappers when they are kept
+pub(crate) fn is_std_type(path: &Path) -> bool {
+    let is_std = |ident: &Ident| ["std", "core", "alloc"].iter().any(|s| ident == s);
+    match (path.segments.first(), path.segments.last()) {
+        (Some(first), _) if path.segments.len() > 1 && is_std(&first.ident) => true,
+        (_, Some(last)) => {
+            let name = last.ident.to_string();
+            STD_TYPES.contains(&name.as_str())
+                || CONTAINERS.contains(&name.as_str())
+                || Wrapper::from_name(&name).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// The different types of a field a `rename`, `add_start`, `add_end` or `rename_all` would be
+/// applied to
+fn rename_targets<'t>(ty: &'t Type, atts: &AttributeOptions, targets: &mut Vec<&'t Ident>) {
+    let mut args = |segment: &'t PathSegment| {
+        for ty in type_arguments(segment) {
+            rename_targets(ty, atts, targets);
+        }
+    };
+    match ty {
+        Type::Path(p) if p.qself.is_none() => {
+            let segment = match p.path.segments.last() {
+                Some(segment) => segment,
+                None => return,
+            };
+            let ident = &segment.ident;
+            let wrapper = Wrapper::find(&p.path, atts);
+            if wrapper == Some(Wrapper::Cow) && !Wrapper::Cow.is_kept(segment, atts) {
+                // Only the owned type is kept which is never renamed
+            } else if wrapper.is_some()
+                || is_std_type(&p.path)
+                || atts.unwrap.iter().any(|(name, _)| ident == name)
+                || atts.map.iter().any(|(name, _, _)| ident == name)
+                || atts.is_excluded(ident)
+            {
+                args(segment)
+            } else if atts.mapped_name(ident).is_none() && !targets.contains(&ident) {
+                targets.push(ident)
+            }
+        }
+        Type::Tuple(t) => {
+            for elem in &t.elems {
+                rename_targets(elem, atts, targets);
+            }
+        }
+        Type::Array(a) => rename_targets(&a.elem, atts, targets),
+        Type::Slice(s) => rename_targets(&s.elem, atts, targets),
+        Type::Reference(r) => rename_targets(&r.elem, atts, targets),
+        Type::Paren(p) => rename_targets(&p.elem, atts, targets),
+        Type::Group(g) => rename_targets(&g.elem, atts, targets),
+        _ => (),
+    }
+}
+
+fn map_type(
+    ty: &Type,
+    atts: &AttributeOptions,
+    naming: Naming,
+    renaming: Renaming,
+) -> Result<FieldType> {
+    let map = |ty: &Type| map_type(ty, atts, naming, renaming);
+    // Anything designal doesn't change is kept exactly as it was written
+    let unchanged = || FieldType::Other {
+        signal: quote! { #ty },
+        renamed: None,
+    };
+    let ty = match ty {
+        // An associated type like `<T as Trait>::Mutable` isn't the wrapper so is left alone
+        Type::Path(p) if p.qself.is_some() => unchanged(),
+        Type::Path(p) => remove_type_wrappers(&p.path, atts, naming, renaming)?,
+        Type::Reference(r) => FieldType::Reference(r.clone(), Box::new(map(&r.elem)?)),
+        Type::Tuple(t) => FieldType::Tuple(t.elems.iter().map(map).collect::<Result<_>>()?),
+        Type::Array(a) => FieldType::Array(a.clone(), Box::new(map(&a.elem)?)),
+        Type::Slice(s) => FieldType::Slice(s.clone(), Box::new(map(&s.elem)?)),
+        // The brackets are no longer needed once the inner type is mapped
+        Type::Paren(p) => map(&p.elem)?,
+        Type::Group(g) => map(&g.elem)?,
+        _ => unchanged(),
+    };
+    let is_unchanged = match &ty {
+        FieldType::Reference(_, inner)
+        | FieldType::Array(_, inner)
+        | FieldType::Slice(_, inner) => inner.is_unchanged(),
+        FieldType::Tuple(elems) => elems.iter().all(FieldType::is_unchanged),
+        ty => ty.is_unchanged(),
+    };
+    match is_unchanged {
+        true => Ok(unchanged()),
+        false => Ok(ty),
+    }
+}
+
+fn map_generic_argument(
+    arg: &GenericArgument,
+    atts: &AttributeOptions,
+    naming: Naming,
+    renaming: Renaming,
+) -> Result<FieldType> {
+    match arg {
+        GenericArgument::Type(ty) => map_type(ty, atts, naming, renaming),
+        arg => Ok(FieldType::Other {
+            signal: quote! {#arg},
+            renamed: None,
+        }),
+    }
+}
+
 fn make_final_type(
-    angle_args: &AngleBracketedGenericArguments,
+    path: &Path,
+    segment: &PathSegment,
+    wrapper: Wrapper,
     atts: &AttributeOptions,
     naming: Naming,
-) -> Result<TokenStream> {
-    let args = &angle_args.args;
-    // Rc, Arc, Mutable, MutableVec
-    if args.len() == 1 {
-        if let Some(span) = atts.hashmap {
-            return Err(Error::new(
-                span,
-                "Use of `hashmap` on a non `MutableBTreeMap<K, V>`",
-            ));
+    renaming: Renaming,
+) -> Result<FieldType> {
+    let map = |ty: &Type| -> Result<Box<FieldType>> {
+        Ok(Box::new(map_type(ty, atts, naming, renaming)?))
+    };
+    if let (Some(span), false) = (atts.hashmap, wrapper == Wrapper::MutableBTreeMap) {
+        return Err(Error::new(
+            span,
+            "Use of `hashmap` on a non `MutableBTreeMap<K, V>`",
+        ));
+    }
+    let path = path.clone();
+    match (wrapper, type_arguments(segment).as_slice()) {
+        (Wrapper::Mutable, [inner]) => Ok(FieldType::Mutable(path, map(inner)?)),
+        (Wrapper::MutableVec, [inner]) => Ok(FieldType::MutableVec(path, map(inner)?)),
+        (Wrapper::Cell, [inner]) => Ok(FieldType::Cell(path, map(inner)?)),
+        (Wrapper::RefCell, [inner]) => Ok(FieldType::RefCell(path, map(inner)?)),
+        (Wrapper::ReadOnlyMutable, [inner]) => Ok(FieldType::ReadOnlyMutable(map(inner)?)),
+        (Wrapper::Weak, [inner]) => Ok(FieldType::Weak(path, map(inner)?)),
+        (Wrapper::Rc | Wrapper::Arc | Wrapper::Box, [inner]) => {
+            Ok(FieldType::Pointer(path, map(inner)?))
         }
-        match args.first().unwrap() {
-            syn::GenericArgument::Type(syn::Type::Path(p)) => {
-                remove_type_wrappers(&p.path, atts, naming)
+        (Wrapper::Cow, [borrowed]) => Ok(FieldType::Cow(path, owned_type(borrowed))),
+        (Wrapper::MutableBTreeMap, [key, value]) => {
+            let key = map(key)?;
+            let hashmap = atts.hashmap.is_some();
+            match value {
+                // This is when the value is unit -> Map to a HashSet / BTreeSet
+                Type::Tuple(value) if value.elems.is_empty() => {
+                    Ok(FieldType::MutableBTreeSet { path, key, hashmap })
+                }
+                _ => Ok(FieldType::MutableBTreeMap {
+                    path,
+                    key,
+                    value: map(value)?,
+                    hashmap,
+                }),
             }
-            _ => Ok(quote! {#args}),
         }
-    // MutableBTreeMap
-    } else if args.len() == 2 {
-        match (args.first().unwrap(), args.last().unwrap()) {
-            (syn::GenericArgument::Type(key), syn::GenericArgument::Type(value)) => {
-                match (key, value) {
-                    (syn::Type::Path(key), syn::Type::Path(value)) => {
-                        let key = remove_type_wrappers(&key.path, atts, naming)?;
-                        let value = remove_type_wrappers(&value.path, atts, naming)?;
-                        // For hygine this must always be the full path
-                        match atts.hashmap {
-                            Some(_) => Ok(quote! { std::collections::HashMap<#key, #value> }),
-                            None => Ok(quote! { std::collections::BTreeMap<#key, #value> }),
-                        }
-                    }
-                    // This is when the value is unit -> Map to a HashSet / BTreeSet
-                    (syn::Type::Path(key), syn::Type::Tuple(value)) if value.elems.is_empty() => {
-                        match atts.hashmap {
-                            Some(_) => Ok(quote! { std::collections::HashSet<#key> }),
-                            None => Ok(quote! { std::collections::BTreeSet<#key> }),
-                        }
-                    }
-                    _ => Ok(quote! {#args}),
-                }
+        _ => {
+            let mut msg = format!(
+                "`{}` must have {} to be removed",
+                segment.ident,
+                wrapper.expected_arguments()
+            );
+            if path.segments.len() == 1 {
+                msg += &format!(
+                    ". If it's a different type use its full path like `crate::{}`",
+                    segment.ident
+                );
             }
-            _ => Ok(quote! {#args}),
+            Err(Error::new(segment.ident.span(), msg))
         }
+    }
+}
+
+fn rename_segment(
+    ident: &Ident,
+    atts: &AttributeOptions,
+    naming: Naming,
+    renaming: Renaming,
+) -> Result<Option<Ident>> {
+    if let Some(name) = atts.mapped_name(ident) {
+        return Ok(Some(name));
+    }
+    let renamer = match &atts.renamer {
+        Some(renamer) if renamer.matches(ident) && !atts.is_excluded(ident) => renamer,
+        _ => return Ok(None),
+    };
+    let is_trim_or_replace = matches!(
+        renamer,
+        Renamer::TrimStart(..)
+            | Renamer::TrimStartAll(..)
+            | Renamer::TrimEnd(..)
+            | Renamer::TrimEndAll(..)
+            | Renamer::Replace(..)
+    );
+    if let (Renaming::Argument, false) = (renaming, is_trim_or_replace) {
+        return Ok(None);
+    }
+    let final_ty_name = renamer.make_new_name(ident, AttributeLocation::Field(naming))?;
+    if final_ty_name != *ident {
+        Ok(Some(final_ty_name))
     } else {
-        unreachable!()
+        Ok(None)
     }
 }
 
-fn remove_type_wrappers(
+/// This is the final path it comes down to after recursion. The renamer is applied & any
+/// generic arguments are mapped in turn.
+fn map_final_path(
     path: &Path,
+    segment: &PathSegment,
     atts: &AttributeOptions,
     naming: Naming,
-) -> Result<TokenStream> {
-    match path.segments.last() {
-        Some(s) => {
-            if s.ident == "Mutable"
-                || (s.ident == "Rc" && atts.keep_rc.is_none())
-                || (s.ident == "Arc" && atts.keep_arc.is_none())
-            {
-                match &s.arguments {
-                    PathArguments::AngleBracketed(angle_args) => {
-                        make_final_type(angle_args, atts, naming)
-                    }
-                    _ => unreachable!(),
-                }
-            } else if s.ident == "MutableVec" {
-                match &s.arguments {
-                    PathArguments::AngleBracketed(angle_args) => {
-                        make_final_type(angle_args, atts, naming).map(|args| quote! { Vec<#args> })
-                    }
-                    _ => unreachable!(),
-                }
-            } else if s.ident == "MutableBTreeMap" {
-                match &s.arguments {
-                    PathArguments::AngleBracketed(angle_args) => {
-                        make_final_type(angle_args, atts, naming)
-                    }
-                    _ => unreachable!(),
-                }
-            // This is the final path it comes down after recursion
-            } else {
-                match &atts.renamer {
-                    Some(renamer) => {
-                        let final_ty_name =
-                            renamer.make_new_name(&s.ident, AttributeLocation::Field(naming))?;
-                        // Need to add back any further types in <T> after the name. eg. Option<i32>
-                        let args = &s.arguments;
-                        Ok(quote! { #final_ty_name#args})
-                    }
-                    None => Ok(quote! { #path }),
-                }
+    renaming: Renaming,
+) -> Result<FieldType> {
+    let (renamed, renaming) = match (&segment.arguments, renaming) {
+        _ if is_std_type(path) => (None, renaming),
+        (PathArguments::AngleBracketed(_), Renaming::Field) => {
+            match rename_segment(&segment.ident, atts, naming, Renaming::Generic)? {
+                Some(name) => (Some(name), Renaming::Argument),
+                None => (None, Renaming::Field),
             }
         }
-        None => unreachable!(),
+        _ => (
+            rename_segment(&segment.ident, atts, naming, renaming)?,
+            Renaming::Argument,
+        ),
+    };
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(angle_args) => angle_args
+            .args
+            .iter()
+            .map(|arg| map_generic_argument(arg, atts, naming, renaming))
+            .collect::<Result<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+    // Any module the type is in is kept eg. `crate::TasteSignal` -> `crate::Taste`
+    let mut renamed_path = path.clone();
+    // Paths always have a last segment
+    let last = renamed_path.segments.last_mut().unwrap();
+    if let Some(name) = &renamed {
+        last.ident = name.clone();
+    }
+    if args.iter().all(FieldType::is_unchanged) {
+        // The arguments in <T> after the name are kept eg. Option<i32>
+        return Ok(FieldType::Other {
+            signal: quote! { #path },
+            renamed: renamed.map(|_| quote! { #renamed_path }),
+        });
+    }
+    last.arguments = PathArguments::None;
+    let ident = quote! { #renamed_path };
+    Ok(FieldType::Generic {
+        signal: path.clone(),
+        ident,
+        renamed: renamed.is_some(),
+        args,
+    })
+}
+
+/// What `ToOwned::Owned` is for the type borrowed by a `Cow`
+fn owned_type(borrowed: &Type) -> TokenStream {
+    match borrowed {
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            quote! { Vec<#elem> }
+        }
+        Type::Path(p) if p.path.is_ident("str") => quote! { String },
+        Type::Path(p) if p.path.is_ident("Path") => quote! { std::path::PathBuf },
+        Type::Path(p) if p.path.is_ident("OsStr") => quote! { std::ffi::OsString },
+        Type::Path(p) if p.path.is_ident("CStr") => quote! { std::ffi::CString },
+        // Anything else is `Clone` so is its own owned type
+        ty => quote! { #ty },
+    }
+}
+
+/// The types in the `<..>` of a path segment
+fn type_arguments(segment: &PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(angle_args) => angle_args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn remove_type_wrappers(
+    path: &Path,
+    atts: &AttributeOptions,
+    naming: Naming,
+    renaming: Renaming,
+) -> Result<FieldType> {
+    let s = match path.segments.last() {
+        Some(s) => s,
+        None => return Err(Error::new(path.span(), "Expected the name of a type")),
+    };
+    if let Some(wrapper) = Wrapper::find(path, atts).filter(|w| !w.is_kept(s, atts)) {
+        make_final_type(path, s, wrapper, atts, naming, renaming)
+    } else if atts.unwrap.iter().any(|(name, _)| s.ident == name) {
+        match type_arguments(s).as_slice() {
+            [inner] => Ok(FieldType::Unwrapped(
+                path.clone(),
+                Box::new(map_type(inner, atts, naming, renaming)?),
+            )),
+            _ => Err(Error::new(
+                s.ident.span(),
+                format!(
+                    "`{}` must have a single type argument to be unwrapped",
+                    s.ident
+                ),
+            )),
+        }
+    } else if let Some((_, to, _)) = atts.map.iter().find(|(name, _, _)| s.ident == name) {
+        let args = match &s.arguments {
+            PathArguments::AngleBracketed(angle_args) => angle_args
+                .args
+                .iter()
+                .map(|arg| map_generic_argument(arg, atts, naming, renaming))
+                .collect::<Result<Vec<_>>>()?,
+            _ => Vec::new(),
+        };
+        Ok(FieldType::Mapped {
+            signal: path.clone(),
+            to: to.clone(),
+            args,
+        })
+    } else {
+        map_final_path(path, s, atts, naming, renaming)
+    }
+}
+
+/// A field of the signal type alongside what designal is going to do with it
+pub(crate) struct MappedField<'a> {
+    pub(crate) field: &'a Field,
+    /// How the field is accessed on the signal type
+    pub(crate) member: Member,
+    /// The name of the field on the designaled type
+    pub(crate) ident: Option<Ident>,
+    pub(crate) atts: AttributeOptions<'a>,
+    /// This is `None` when the field is ignored or removed
+    pub(crate) ty: Option<FieldType>,
+}
+
+impl<'a> MappedField<'a> {
+    pub(crate) fn is_removed(&self) -> bool {
+        self.atts.remove.is_some()
     }
 }
 
-fn clean_field(
+fn clean_field(mapped: &MappedField) -> TokenStream {
+    let MappedField {
+        field, ident, atts, ..
+    } = mapped;
+    let ty = match &mapped.ty {
+        Some(ty) => quote! { #ty },
+        None => {
+            let ty = &field.ty;
+            quote! { #ty }
+        }
+    };
+    make_field(field, ident.as_ref(), atts, ty)
+}
+
+/// Writes out a field with its new type & attributes
+pub(crate) fn make_field(
     field: &Field,
+    ident: Option<&Ident>,
     atts: &AttributeOptions,
-    final_type: Option<Result<TokenStream>>,
-) -> Result<TokenStream> {
-    let vis = &field.vis;
-    let default_ty = &field.ty;
-    let ty = final_type.unwrap_or_else(|| Ok(quote! { #default_ty }))?;
+    ty: TokenStream,
+) -> TokenStream {
+    let vis = match &atts.vis {
+        Some((vis, _)) => vis,
+        None => &field.vis,
+    };
     let (designal_atts, replace_atts) = &atts.designal_attributes;
     let xs = vec![];
     let current_atts = if *replace_atts {
@@ -134,42 +755,92 @@ fn clean_field(
     } else {
         &atts.current_attributes
     };
-    match &field.ident {
-        Some(name) => Ok(quote! {
+    match ident {
+        Some(name) => quote! {
             #(#designal_atts)*
             #(#current_atts)*
             #vis #name: #ty
-        }),
-        None => Ok(quote! {
+        },
+        None => quote! {
             #(#designal_atts)*
             #(#current_atts)*
             #vis #ty
-        }),
+        },
     }
 }
 
-fn map_field(
-    field: &Field,
+fn map_field<'a>(
+    (index, field): (usize, &'a Field),
     naming: Naming,
     type_atts: &AttributeOptions,
-) -> Option<Result<TokenStream>> {
-    let atts = match AttributeOptions::new(&field.attrs, AttributeLocation::Field(naming)) {
-        Ok(atts) => atts.add_type_level_to_field_level(type_atts),
-        Err(e) => return Some(Err(e)),
+) -> Result<MappedField<'a>> {
+    let atts = AttributeOptions::new(
+        &field.attrs,
+        AttributeLocation::Field(naming),
+        Derive::Designal,
+    )?
+    .add_type_level_to_field_level(type_atts)?;
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(index.into()),
     };
-    if atts.remove.is_some() {
+    let ident = rename_field(field, &atts)?;
+    let ty = if atts.remove.is_some() || atts.ignore.is_some() {
         None
-    } else if atts.ignore.is_some() {
-        Some(clean_field(&field, &atts, None))
     } else {
-        let tokens = if let syn::Type::Path(p) = &field.ty {
-            let final_type = Some(remove_type_wrappers(&p.path, &atts, naming));
-            clean_field(&field, &atts, final_type)
-        } else {
-            clean_field(&field, &atts, None)
-        };
-        Some(tokens)
-    }
+        check_rename_target(&field.ty, &member, &atts)?;
+        let ty = map_type(&field.ty, &atts, naming, Renaming::Field)?;
+        if let (Some(renamer), false) = (&atts.renamer, ty.is_renamed()) {
+            let e = match (renamer, atts.nested) {
+                // Only the types in the container's `for` are renamed
+                (Renamer::AddStartAll(_, span) | Renamer::AddEndAll(_, span), Some(_)) => {
+                    Some(Error::new(
+                        *span,
+                        format!(
+                            "field {} has no type listed in `for`",
+                            member.to_token_stream()
+                        ),
+                    ))
+                }
+                _ => renamer.err_no_match(&member),
+            };
+            if let Some(e) = e {
+                // A nested field was given the renamer by the container
+                return Err(match atts.nested {
+                    Some(span) => Error::new(span, e),
+                    None => e,
+                });
+            }
+        }
+        Some(ty)
+    };
+    Ok(MappedField {
+        field,
+        member,
+        ident,
+        atts,
+        ty,
+    })
+}
+
+fn map_fields<'a>(
+    fields: &'a Fields,
+    naming: Naming,
+    type_atts: &AttributeOptions,
+) -> Result<Vec<MappedField<'a>>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|field| map_field(field, naming, type_atts))
+        .collect_all()
+}
+
+fn field_tokens(fields: &[MappedField]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .filter(|field| !field.is_removed())
+        .map(clean_field)
+        .collect()
 }
 
 fn build_struct(
@@ -178,29 +849,18 @@ fn build_struct(
     input: &DeriveInput,
     type_atts: &AttributeOptions,
 ) -> Result<TokenStream> {
-    let naming = match data.fields {
-        syn::Fields::Named(_) => Naming::Named,
-        syn::Fields::Unnamed(_) | syn::Fields::Unit => Naming::Unnamed,
-    };
-    let vis = &input.vis;
-    let generics = &input.generics;
-    let wher = &input.generics.where_clause;
+    let naming = Naming::of(&data.fields);
+    let vis = type_vis(input, type_atts);
     // TODO: Should just push the others on here & DRY this code..
-    let (designal_atts, replace_atts) = &type_atts.designal_attributes;
-    let fields = {
-        let xs = data
-            .fields
-            .iter()
-            .filter_map(|field| map_field(field, naming, &type_atts))
-            .collect::<Result<Vec<TokenStream>>>()?;
-        quote! { #(#xs),* }
-    };
-    let xs = vec![];
-    let current_atts = if *replace_atts {
-        &xs
-    } else {
-        &type_atts.current_attributes
-    };
+    let (designal_atts, _) = &type_atts.designal_attributes;
+    let mapped = map_fields(&data.fields, naming, type_atts)?;
+    let xs = field_tokens(&mapped);
+    let generics = rename_generics(&input.generics, type_atts)?;
+    let generics = generics::designal_generics(&generics, &xs, type_atts)?;
+    let wher = &generics.where_clause;
+    let fields = quote! { #(#xs),* };
+    let current_atts = kept_attributes(type_atts);
+    let conversions = convert::build_struct(&name, &generics, input, &mapped, type_atts)?;
     Ok(match naming {
         Naming::Named => {
             quote! {
@@ -210,6 +870,7 @@ fn build_struct(
                 #wher {
                     #fields
                 }
+                #conversions
             }
         }
         Naming::Unnamed => {
@@ -218,24 +879,78 @@ fn build_struct(
                 #(#current_atts)*
                 #vis struct #name #generics (#fields)
                 #wher;
+                #conversions
             }
         }
     })
 }
 
-fn map_enum_variant(variant: &Variant, type_atts: &AttributeOptions) -> Result<TokenStream> {
-    let ident = &variant.ident;
-    let disc = &variant.discriminant;
-    let atts = &variant.attrs;
+/// A variant of the signal enum alongside its mapped fields
+pub(crate) struct MappedVariant<'a> {
+    pub(crate) variant: &'a Variant,
+    /// The name of the variant on the designaled enum
+    pub(crate) ident: Ident,
+    /// Set when the variant isn't on the designaled enum
+    pub(crate) remove: Option<Span>,
+    /// The attributes of the variant on the designaled enum
+    pub(crate) attributes: Vec<TokenStream>,
+    pub(crate) fields: Vec<MappedField<'a>>,
+}
+
+impl MappedVariant<'_> {
+    pub(crate) fn is_removed(&self) -> bool {
+        self.remove.is_some()
+    }
+}
+
+fn map_enum_variant<'a>(
+    variant: &'a Variant,
+    type_atts: &AttributeOptions,
+) -> Result<MappedVariant<'a>> {
+    let atts = AttributeOptions::new(&variant.attrs, AttributeLocation::Variant, Derive::Designal)?;
+    let ident = match &atts.renamer {
+        Some(renamer) => renamer.make_new_name(&variant.ident, AttributeLocation::Variant)?,
+        None => variant.ident.clone(),
+    };
+    let (designal_atts, replace_atts) = &atts.designal_attributes;
+    let mut attributes = designal_atts.clone();
+    if !replace_atts {
+        attributes.extend(atts.current_attributes.iter().map(|att| quote! { #att }));
+    }
+    let remove = atts.remove;
+    let variant_atts = atts.add_type_level_to_variant_level(type_atts)?;
+    let fields = map_fields(&variant.fields, Naming::of(&variant.fields), &variant_atts)?;
+    if let Some((_, span)) = fields.iter().find_map(|field| field.atts.vis.as_ref()) {
+        return Err(Error::new(
+            *span,
+            "The fields of an enum variant can't be given a visibility",
+        ));
+    }
+    Ok(MappedVariant {
+        variant,
+        ident,
+        remove,
+        attributes,
+        fields,
+    })
+}
+
+fn variant_tokens(mapped: &MappedVariant) -> TokenStream {
+    let MappedVariant {
+        variant,
+        ident,
+        attributes,
+        fields,
+        ..
+    } = mapped;
+    let discriminant = variant
+        .discriminant
+        .as_ref()
+        .map(|(eq, expr)| quote! { #eq #expr });
     let fields = if variant.fields.is_empty() {
         quote! {}
     } else {
-        let xs = variant
-            .fields
-            .iter()
-            // Enums must always be treated as Named for map_field
-            .filter_map(|field| map_field(field, Naming::Named, &type_atts))
-            .collect::<Result<Vec<TokenStream>>>()?;
+        let xs = field_tokens(fields);
         match &variant.fields {
             syn::Fields::Named(_) => {
                 quote! { {#(#xs),*} }
@@ -245,17 +960,11 @@ fn map_enum_variant(variant: &Variant, type_atts: &AttributeOptions) -> Result<T
             }
         }
     };
-
-    match disc {
-        Some(_) => Err(Error::new(
-            variant.span(),
-            "Discriminated variants are not yet implemented",
-        )),
-        None => Ok(quote! {
-            #(#atts)*
-            #ident
-            #fields,
-        }),
+    quote! {
+        #(#attributes)*
+        #ident
+        #fields
+        #discriminant,
     }
 }
 
@@ -265,26 +974,26 @@ fn build_enum(
     input: &DeriveInput,
     type_atts: &AttributeOptions,
 ) -> Result<TokenStream> {
-    let vis = &input.vis;
-    let generics = &input.generics;
-    let wher = &input.generics.where_clause;
-    let (designal_atts, replace_atts) = &type_atts.designal_attributes;
-    let variants = {
-        let xs = data
-            .variants
-            .iter()
-            .map(|variant| map_enum_variant(variant, type_atts))
-            .collect::<Result<Vec<TokenStream>>>()?;
-        quote! {
-            #(#xs)*
-        }
-    };
-    let xs = vec![];
-    let current_atts = if *replace_atts {
-        &xs
-    } else {
-        &type_atts.current_attributes
+    let vis = type_vis(input, type_atts);
+    let (designal_atts, _) = &type_atts.designal_attributes;
+    let mapped = data
+        .variants
+        .iter()
+        .map(|variant| map_enum_variant(variant, type_atts))
+        .collect_all()?;
+    let xs: Vec<_> = mapped
+        .iter()
+        .filter(|variant| !variant.is_removed())
+        .map(variant_tokens)
+        .collect();
+    let generics = rename_generics(&input.generics, type_atts)?;
+    let generics = generics::designal_generics(&generics, &xs, type_atts)?;
+    let wher = &generics.where_clause;
+    let variants = quote! {
+        #(#xs)*
     };
+    let current_atts = kept_attributes(type_atts);
+    let conversions = convert::build_enum(&name, &generics, input, &mapped, type_atts)?;
     Ok(quote! {
         #(#designal_atts)*
         #(#current_atts)*
@@ -292,10 +1001,94 @@ fn build_enum(
         #wher {
             #variants
         }
+        #conversions
     })
 }
 
-fn rename_type(ident: &Ident, attr: &AttributeOptions) -> Result<Ident> {
+/// A type in the generics mapped the same way as the type of a field
+fn designal_type(ty: &Type, atts: &AttributeOptions) -> Result<Type> {
+    let ty = map_type(ty, atts, Naming::Named, Renaming::Field)?;
+    syn::parse2(quote! { #ty })
+}
+
+/// The type arguments of any traits eg. `From<TasteSignal>` -> `From<Taste>`
+fn designal_bounds(
+    bounds: &mut Punctuated<TypeParamBound, Token![+]>,
+    atts: &AttributeOptions,
+) -> Result<()> {
+    for bound in bounds.iter_mut() {
+        if let TypeParamBound::Trait(t) = bound {
+            for segment in t.path.segments.iter_mut() {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in args.args.iter_mut() {
+                        match arg {
+                            GenericArgument::Type(ty) => *ty = designal_type(ty, atts)?,
+                            GenericArgument::Binding(b) => b.ty = designal_type(&b.ty, atts)?,
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The types in the defaults, bounds & where clause are renamed like the types of the fields
+fn rename_generics(generics: &Generics, type_atts: &AttributeOptions) -> Result<Generics> {
+    let atts = AttributeOptions::new(
+        &[],
+        AttributeLocation::Field(Naming::Named),
+        Derive::Designal,
+    )?
+    .add_type_level_to_field_level(type_atts)?;
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            designal_bounds(&mut param.bounds, &atts)?;
+            if let Some(default) = &mut param.default {
+                *default = designal_type(default, &atts)?;
+            }
+        }
+    }
+    if let Some(wher) = &mut generics.where_clause {
+        for predicate in wher.predicates.iter_mut() {
+            if let WherePredicate::Type(predicate) = predicate {
+                predicate.bounded_ty = designal_type(&predicate.bounded_ty, &atts)?;
+                designal_bounds(&mut predicate.bounds, &atts)?;
+            }
+        }
+    }
+    Ok(generics)
+}
+
+/// The attributes of the signal type that are kept, with `repr` replacing any `#[repr(..)]`
+fn kept_attributes(type_atts: &AttributeOptions) -> Vec<TokenStream> {
+    let (_, replace_atts) = &type_atts.designal_attributes;
+    let mut atts: Vec<TokenStream> = match replace_atts {
+        true => Vec::new(),
+        false => type_atts
+            .current_attributes
+            .iter()
+            .filter(|att| type_atts.repr.is_none() || !att.path.is_ident("repr"))
+            .map(|att| quote! { #att })
+            .collect(),
+    };
+    if let Some((repr, _)) = &type_atts.repr {
+        atts.push(quote! { #[repr(#repr)] });
+    }
+    atts
+}
+
+/// The visibility of the generated type
+pub(crate) fn type_vis<'a>(input: &'a DeriveInput, atts: &'a AttributeOptions) -> &'a Visibility {
+    match &atts.vis {
+        Some((vis, _)) => vis,
+        None => &input.vis,
+    }
+}
+
+pub(crate) fn rename_type(ident: &Ident, attr: &AttributeOptions) -> Result<Ident> {
     // Safe to unwrap since is checked in validation of attributes
     let renamer = attr.renamer.as_ref().unwrap();
     let name = renamer.make_new_name(&ident, AttributeLocation::Type(ident.span()))?;
@@ -309,10 +1102,23 @@ fn rename_type(ident: &Ident, attr: &AttributeOptions) -> Result<Ident> {
     }
 }
 
+/// The field's name once any field renamer is applied
+pub(crate) fn rename_field(field: &Field, atts: &AttributeOptions) -> Result<Option<Ident>> {
+    match (&field.ident, &atts.field_renamer) {
+        (Some(ident), Some(renamer)) => Ok(Some(
+            renamer.make_new_name(ident, AttributeLocation::Field(Naming::Named))?,
+        )),
+        (ident, _) => Ok(ident.clone()),
+    }
+}
+
 pub(crate) fn parse_input(input: DeriveInput) -> Result<TokenStream> {
-    let type_atts =
-        AttributeOptions::new(&input.attrs, AttributeLocation::Type(input.ident.span()))?;
-    let name = rename_type(&input.ident, &type_atts)?;
+    let type_atts = AttributeOptions::new_container(&input, Derive::Designal)?;
+    let mut errors = Errors::default();
+    // The fields are still checked with the original name so their errors are reported too
+    let name = errors
+        .check(rename_type(&input.ident, &type_atts))
+        .unwrap_or_else(|| input.ident.clone());
     let tokens = match &input.data {
         syn::Data::Struct(data) => build_struct(name, data, &input, &type_atts),
         syn::Data::Enum(data) => build_enum(name, data, &input, &type_atts),
@@ -320,6 +1126,8 @@ pub(crate) fn parse_input(input: DeriveInput) -> Result<TokenStream> {
             input.ident.span(),
             "Unions are not yet supported",
         )),
-    }?;
-    Ok(tokens)
+    };
+    let tokens = errors.check(tokens);
+    errors.finish()?;
+    Ok(tokens.unwrap_or_default())
 }
@@ -0,0 +1,935 @@
+//! Generates the conversions between the signal type and the designaled type
+use crate::attributes::AttributeOptions;
+use crate::builder::{FieldType, MappedField, MappedVariant};
+use crate::errors::CollectAll;
+use crate::generics;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    spanned::Spanned, DeriveInput, Error, Generics, Ident, Index, Member, Path, Result,
+    TypeReference, TypeSlice,
+};
+
+/// A field that is kept on the designaled type and how it is reached on both types
+struct KeptField<'a> {
+    mapped: &'a MappedField<'a>,
+    /// How the field is accessed on the designaled type
+    member: Member,
+    /// Name used when destructuring an enum variant
+    binding: Ident,
+}
+
+fn kept_fields<'a>(fields: &'a [MappedField<'a>]) -> Vec<KeptField<'a>> {
+    fields
+        .iter()
+        .filter(|mapped| !mapped.is_removed())
+        .enumerate()
+        .map(|(index, mapped)| {
+            let member = match &mapped.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            KeptField {
+                mapped,
+                member,
+                binding: format_ident!("field_{}", index),
+            }
+        })
+        .collect()
+}
+
+fn last_ident(path: &Path) -> String {
+    path.segments
+        .last()
+        .map(|s| s.ident.to_string())
+        .unwrap_or_default()
+}
+
+fn err_no_conversion(path: &Path) -> Error {
+    Error::new(
+        path.span(),
+        "Only the arguments of std types like `Option<T>` & `Vec<T>` can be converted",
+    )
+}
+
+fn err_reference(ty: &TypeReference) -> Error {
+    Error::new(
+        ty.span(),
+        "A reference to a changed type can't be converted",
+    )
+}
+
+fn err_slice(ty: &TypeSlice) -> Error {
+    Error::new(ty.span(), "A slice of a changed type can't be converted")
+}
+
+fn err_unwrapped(path: &Path) -> Error {
+    Error::new(
+        path.span(),
+        "A type removed with `unwrap` can't be converted",
+    )
+}
+
+/// Takes an expression that is a reference to the signal type and reads out an owned copy of
+/// the designaled type
+fn snapshot_type(ty: &FieldType, value: TokenStream) -> Result<TokenStream> {
+    Ok(match ty {
+        FieldType::Mutable(_, inner) | FieldType::ReadOnlyMutable(inner) => {
+            let inner = snapshot_type(inner, quote! { &*v })?;
+            quote! {{
+                let v = (#value).lock_ref();
+                #inner
+            }}
+        }
+        FieldType::Cell(_, inner) => {
+            let inner = snapshot_type(inner, quote! { &v })?;
+            quote! {{
+                let v = (#value).get();
+                #inner
+            }}
+        }
+        FieldType::RefCell(_, inner) => {
+            let inner = snapshot_type(inner, quote! { &*v })?;
+            quote! {{
+                let v = (#value).borrow();
+                #inner
+            }}
+        }
+        FieldType::Weak(_, inner) => {
+            let inner = snapshot_type(inner, quote! { &*v })?;
+            quote! { (#value).upgrade().map(|v| #inner) }
+        }
+        FieldType::Cow(..) => quote! { std::borrow::ToOwned::to_owned(&**#value) },
+        FieldType::MutableVec(_, inner) => {
+            let inner = snapshot_type(inner, quote! { v })?;
+            quote! {{
+                let v = (#value).lock_ref();
+                v.iter().map(|v| #inner).collect::<std::vec::Vec<_>>()
+            }}
+        }
+        FieldType::MutableBTreeMap {
+            key,
+            value: v,
+            hashmap,
+            ..
+        } => {
+            let key = snapshot_type(key, quote! { k })?;
+            let v = snapshot_type(v, quote! { v })?;
+            let collection = match hashmap {
+                true => quote! { std::collections::HashMap<_, _> },
+                false => quote! { std::collections::BTreeMap<_, _> },
+            };
+            quote! {{
+                let v = (#value).lock_ref();
+                v.iter().map(|(k, v)| (#key, #v)).collect::<#collection>()
+            }}
+        }
+        FieldType::MutableBTreeSet { key, hashmap, .. } => {
+            let key = snapshot_type(key, quote! { k })?;
+            let collection = match hashmap {
+                true => quote! { std::collections::HashSet<_> },
+                false => quote! { std::collections::BTreeSet<_> },
+            };
+            quote! {{
+                let v = (#value).lock_ref();
+                v.keys().map(|k| #key).collect::<#collection>()
+            }}
+        }
+        FieldType::Pointer(_, inner) => snapshot_type(inner, quote! { &**#value })?,
+        FieldType::Generic {
+            signal,
+            renamed: false,
+            args,
+            ..
+        } => {
+            let arg = |index: usize, value: TokenStream| snapshot_type(&args[index], value);
+            match (last_ident(signal).as_str(), args.len()) {
+                ("Option", 1) => {
+                    let v = arg(0, quote! { v })?;
+                    quote! { (#value).as_ref().map(|v| #v) }
+                }
+                ("Result", 2) => {
+                    let v = arg(0, quote! { v })?;
+                    let e = arg(1, quote! { e })?;
+                    quote! { (#value).as_ref().map(|v| #v).map_err(|e| #e) }
+                }
+                ("Box", 1) => {
+                    let v = arg(0, quote! { &**#value })?;
+                    quote! { std::boxed::Box::new(#v) }
+                }
+                ("Vec", 1) | ("VecDeque", 1) | ("BTreeSet", 1) | ("HashSet", 1) => {
+                    let v = arg(0, quote! { v })?;
+                    quote! { (#value).iter().map(|v| #v).collect::<#ty>() }
+                }
+                ("BTreeMap", 2) | ("HashMap", 2) => {
+                    let k = arg(0, quote! { k })?;
+                    let v = arg(1, quote! { v })?;
+                    quote! { (#value).iter().map(|(k, v)| (#k, #v)).collect::<#ty>() }
+                }
+                _ => return Err(err_no_conversion(signal)),
+            }
+        }
+        FieldType::Generic { signal, .. } => return Err(err_no_conversion(signal)),
+        FieldType::Reference(signal, _) => return Err(err_reference(signal)),
+        FieldType::Tuple(elems) => {
+            let elems = elems
+                .iter()
+                .enumerate()
+                .map(|(index, elem)| {
+                    let index = Index::from(index);
+                    snapshot_type(elem, quote! { &v.#index })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {{
+                let v = #value;
+                (#(#elems,)*)
+            }}
+        }
+        FieldType::Array(_, inner) => {
+            let inner = snapshot_type(inner, quote! { &v[i] })?;
+            quote! {{
+                let v = #value;
+                std::array::from_fn(|i| #inner)
+            }}
+        }
+        FieldType::Slice(signal, _) => return Err(err_slice(signal)),
+        FieldType::Unwrapped(signal, _) => return Err(err_unwrapped(signal)),
+        // Mapped types are converted with `From`
+        FieldType::Mapped { .. } => quote! { <#ty as std::convert::From<_>>::from(#value) },
+        FieldType::Other {
+            renamed: Some(renamed),
+            ..
+        } => quote! { <#renamed as std::convert::From<_>>::from(#value) },
+        FieldType::Other { renamed: None, .. } => quote! { std::clone::Clone::clone(#value) },
+    })
+}
+
+fn snapshot_field(field: &KeptField, value: TokenStream) -> Result<TokenStream> {
+    let member = &field.member;
+    let value = match &field.mapped.ty {
+        Some(ty) => snapshot_type(ty, value)?,
+        // Ignored fields are the same on both sides
+        None => quote! { std::clone::Clone::clone(#value) },
+    };
+    Ok(quote! { #member: #value })
+}
+
+fn snapshot_struct(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    fields: &[MappedField],
+) -> Result<TokenStream> {
+    let signal = &input.ident;
+    let merged = generics::impl_generics(&input.generics, designal);
+    let (impl_generics, _, where_clause) = merged.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (_, name_generics, _) = designal.split_for_impl();
+    let fields = kept_fields(fields)
+        .into_iter()
+        .map(|field| {
+            let member = &field.mapped.member;
+            snapshot_field(&field, quote! { &value.#member })
+        })
+        .collect_all()?;
+    Ok(quote! {
+        impl #impl_generics std::convert::From<&#signal #ty_generics> for #name #name_generics
+        #where_clause
+        {
+            fn from(value: &#signal #ty_generics) -> Self {
+                Self { #(#fields),* }
+            }
+        }
+    })
+}
+
+fn snapshot_enum(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    variants: &[MappedVariant],
+) -> Result<TokenStream> {
+    let signal = &input.ident;
+    let merged = generics::impl_generics(&input.generics, designal);
+    let (impl_generics, _, where_clause) = merged.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (_, name_generics, _) = designal.split_for_impl();
+    let arms = variants
+        .iter()
+        .map(|mapped| {
+            let signal_ident = &mapped.variant.ident;
+            let ident = &mapped.ident;
+            let kept = kept_fields(&mapped.fields);
+            let pattern = kept.iter().map(|field| {
+                let member = &field.mapped.member;
+                let binding = &field.binding;
+                quote! { #member: #binding, }
+            });
+            let fields = kept
+                .iter()
+                .map(|field| {
+                    let binding = &field.binding;
+                    snapshot_field(field, quote! { #binding })
+                })
+                .collect_all()?;
+            Ok(quote! {
+                #signal::#signal_ident { #(#pattern)* .. } => Self::#ident { #(#fields),* },
+            })
+        })
+        .collect_all()?;
+    // An empty enum can only be matched on by value
+    let value = match variants.is_empty() {
+        true => quote! { *value },
+        false => quote! { value },
+    };
+    Ok(quote! {
+        impl #impl_generics std::convert::From<&#signal #ty_generics> for #name #name_generics
+        #where_clause
+        {
+            fn from(value: &#signal #ty_generics) -> Self {
+                match #value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Takes an owned expression of the designaled type and wraps it back up into the signal type
+fn rebuild_type(ty: &FieldType, value: TokenStream) -> Result<TokenStream> {
+    Ok(match ty {
+        FieldType::Mutable(path, inner)
+        | FieldType::Pointer(path, inner)
+        | FieldType::Cell(path, inner)
+        | FieldType::RefCell(path, inner) => {
+            let inner = rebuild_type(inner, value)?;
+            quote! { <#path>::new(#inner) }
+        }
+        FieldType::ReadOnlyMutable(inner) => {
+            let inner = rebuild_type(inner, value)?;
+            quote! { futures_signals::signal::Mutable::new(#inner).read_only() }
+        }
+        FieldType::Weak(path, _) => {
+            return Err(Error::new(
+                path.span(),
+                "A `Weak` can't be rebuilt since nothing would own the value",
+            ))
+        }
+        FieldType::Cow(path, _) => quote! { <#path>::Owned(#value) },
+        FieldType::MutableVec(path, _) => {
+            let values = rebuild_values(ty, value)?;
+            quote! { <#path>::new_with_values(#values) }
+        }
+        FieldType::MutableBTreeMap { path, .. } | FieldType::MutableBTreeSet { path, .. } => {
+            let values = rebuild_values(ty, value)?;
+            quote! { <#path>::with_values(#values) }
+        }
+        FieldType::Generic {
+            signal,
+            renamed: false,
+            args,
+            ..
+        } => {
+            let arg = |index: usize, value: TokenStream| rebuild_type(&args[index], value);
+            match (last_ident(signal).as_str(), args.len()) {
+                ("Option", 1) => {
+                    let v = arg(0, quote! { v })?;
+                    quote! { (#value).map(|v| #v) }
+                }
+                ("Result", 2) => {
+                    let v = arg(0, quote! { v })?;
+                    let e = arg(1, quote! { e })?;
+                    quote! { (#value).map(|v| #v).map_err(|e| #e) }
+                }
+                ("Box", 1) => {
+                    let v = arg(0, quote! { *(#value) })?;
+                    quote! { std::boxed::Box::new(#v) }
+                }
+                ("Vec", 1) | ("VecDeque", 1) | ("BTreeSet", 1) | ("HashSet", 1) => {
+                    let v = arg(0, quote! { v })?;
+                    quote! { (#value).into_iter().map(|v| #v).collect::<#signal>() }
+                }
+                ("BTreeMap", 2) | ("HashMap", 2) => {
+                    let k = arg(0, quote! { k })?;
+                    let v = arg(1, quote! { v })?;
+                    quote! { (#value).into_iter().map(|(k, v)| (#k, #v)).collect::<#signal>() }
+                }
+                _ => return Err(err_no_conversion(signal)),
+            }
+        }
+        FieldType::Generic { signal, .. } => return Err(err_no_conversion(signal)),
+        FieldType::Reference(signal, _) => return Err(err_reference(signal)),
+        FieldType::Tuple(elems) => {
+            let elems = elems
+                .iter()
+                .enumerate()
+                .map(|(index, elem)| {
+                    let index = Index::from(index);
+                    rebuild_type(elem, quote! { v.#index })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {{
+                let v = #value;
+                (#(#elems,)*)
+            }}
+        }
+        FieldType::Array(_, inner) => {
+            let inner = rebuild_type(inner, quote! { v })?;
+            quote! { (#value).map(|v| #inner) }
+        }
+        FieldType::Slice(signal, _) => return Err(err_slice(signal)),
+        FieldType::Unwrapped(signal, _) => return Err(err_unwrapped(signal)),
+        FieldType::Mapped { signal, .. } => {
+            quote! { <#signal as std::convert::From<_>>::from(#value) }
+        }
+        FieldType::Other {
+            signal,
+            renamed: Some(_),
+        } => quote! { <#signal as std::convert::From<_>>::from(#value) },
+        FieldType::Other { renamed: None, .. } => value,
+    })
+}
+
+/// The values that go into a `MutableVec` or `MutableBTreeMap` collected from the designaled
+/// collection
+fn rebuild_values(ty: &FieldType, value: TokenStream) -> Result<TokenStream> {
+    Ok(match ty {
+        FieldType::MutableVec(_, inner) => {
+            let inner = rebuild_type(inner, quote! { v })?;
+            quote! { (#value).into_iter().map(|v| #inner).collect() }
+        }
+        FieldType::MutableBTreeMap { key, value: v, .. } => {
+            let key = rebuild_type(key, quote! { k })?;
+            let v = rebuild_type(v, quote! { v })?;
+            quote! { (#value).into_iter().map(|(k, v)| (#key, #v)).collect() }
+        }
+        FieldType::MutableBTreeSet { key, .. } => {
+            let key = rebuild_type(key, quote! { k })?;
+            quote! { (#value).into_iter().map(|k| (#key, ())).collect() }
+        }
+        _ => rebuild_type(ty, value)?,
+    })
+}
+
+/// The fields of the signal type built from the bindings of the designaled type's fields
+fn rebuild_fields(
+    fields: &[MappedField],
+    value: impl Fn(&KeptField) -> TokenStream,
+) -> Result<TokenStream> {
+    let kept = kept_fields(fields);
+    let mut kept = kept.iter();
+    let fields = fields
+        .iter()
+        .map(|mapped| {
+            let member = &mapped.member;
+            let value = if mapped.is_removed() {
+                match &mapped.atts.default {
+                    Some((path, _)) => quote! { #path() },
+                    None => quote! { std::default::Default::default() },
+                }
+            } else {
+                // The kept fields are in the same order as the signal fields
+                let field = kept.next().unwrap();
+                let value = value(field);
+                match &mapped.ty {
+                    Some(ty) => rebuild_type(ty, value)?,
+                    None => value,
+                }
+            };
+            Ok(quote! { #member: #value })
+        })
+        .collect_all()?;
+    Ok(quote! { #(#fields),* })
+}
+
+fn rebuild_struct(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    fields: &[MappedField],
+) -> Result<TokenStream> {
+    let signal = &input.ident;
+    let merged = generics::impl_generics(&input.generics, designal);
+    let (impl_generics, _, where_clause) = merged.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (_, name_generics, _) = designal.split_for_impl();
+    let fields = rebuild_fields(fields, |field| {
+        let member = &field.member;
+        quote! { value.#member }
+    })?;
+    Ok(quote! {
+        impl #impl_generics std::convert::From<#name #name_generics> for #signal #ty_generics
+        #where_clause
+        {
+            fn from(value: #name #name_generics) -> Self {
+                Self { #fields }
+            }
+        }
+    })
+}
+
+fn rebuild_enum(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    variants: &[MappedVariant],
+) -> Result<TokenStream> {
+    let signal = &input.ident;
+    let merged = generics::impl_generics(&input.generics, designal);
+    let (impl_generics, _, where_clause) = merged.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (_, name_generics, _) = designal.split_for_impl();
+    let arms = variants
+        .iter()
+        .filter(|mapped| !mapped.is_removed())
+        .map(|mapped| {
+            let signal_ident = &mapped.variant.ident;
+            let ident = &mapped.ident;
+            let pattern = kept_fields(&mapped.fields).into_iter().map(|field| {
+                let member = &field.member;
+                let binding = &field.binding;
+                quote! { #member: #binding, }
+            });
+            let fields = rebuild_fields(&mapped.fields, |field| {
+                let binding = &field.binding;
+                quote! { #binding }
+            })?;
+            Ok(quote! {
+                #name::#ident { #(#pattern)* } => Self::#signal_ident { #fields },
+            })
+        })
+        .collect_all()?;
+    Ok(quote! {
+        impl #impl_generics std::convert::From<#name #name_generics> for #signal #ty_generics
+        #where_clause
+        {
+            fn from(value: #name #name_generics) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// How the generated update method pushes new values into the signal type
+#[derive(Copy, Clone)]
+enum Update {
+    /// `apply`: every `Mutable` is set and every collection replaced
+    All,
+    /// `apply_neq`: only the values that differ from the current ones are set
+    Changed,
+}
+
+impl Update {
+    fn method(self) -> Ident {
+        match self {
+            Self::All => format_ident!("apply"),
+            Self::Changed => format_ident!("apply_neq"),
+        }
+    }
+}
+
+/// Replaces the contents of a `MutableVec` or `MutableBTreeMap` only emitting the differences.
+/// The values are compared so this is only used when they aren't nested signal types
+fn update_changed_values(ty: &FieldType, target: TokenStream, values: TokenStream) -> TokenStream {
+    match ty {
+        // Positional diff
+        FieldType::MutableVec(..) => quote! {{
+            let values: std::vec::Vec<_> = #values;
+            let len = values.len();
+            let mut lock = (#target).lock_mut();
+            for (index, value) in values.into_iter().enumerate() {
+                if index >= lock.len() {
+                    lock.push_cloned(value);
+                } else if lock[index] != value {
+                    lock.set_cloned(index, value);
+                }
+            }
+            if lock.len() > len {
+                lock.truncate(len);
+            }
+        }},
+        // Keyed diff
+        _ => quote! {{
+            let values: std::collections::BTreeMap<_, _> = #values;
+            let mut lock = (#target).lock_mut();
+            let removed: std::vec::Vec<_> = lock
+                .keys()
+                .filter(|k| !values.contains_key(*k))
+                .cloned()
+                .collect();
+            for k in removed {
+                lock.remove(&k);
+            }
+            for (k, v) in values {
+                if lock.get(&k) != Some(&v) {
+                    lock.insert_cloned(k, v);
+                }
+            }
+        }},
+    }
+}
+
+/// Updates the elements of a `MutableVec` or the values of a `MutableBTreeMap` in place, so any
+/// nested signal types keep the `Mutable`s their subscribers hold. `element` updates the
+/// existing `target` with `value`, new ones are added & any left over removed.
+fn update_in_place(
+    ty: &FieldType,
+    target: TokenStream,
+    value: TokenStream,
+    element: TokenStream,
+) -> Result<Option<TokenStream>> {
+    Ok(match ty {
+        FieldType::MutableVec(_, inner) => {
+            let new = rebuild_type(inner, quote! { value })?;
+            Some(quote! {{
+                let values: std::vec::Vec<_> = std::iter::IntoIterator::into_iter(#value).collect();
+                let len = values.len();
+                let mut lock = (#target).lock_mut();
+                for (index, value) in values.into_iter().enumerate() {
+                    if index < lock.len() {
+                        let target = &lock[index];
+                        #element
+                    } else {
+                        lock.push_cloned(#new);
+                    }
+                }
+                if lock.len() > len {
+                    lock.truncate(len);
+                }
+            }})
+        }
+        FieldType::MutableBTreeMap { key, value: v, .. } => {
+            let key = rebuild_type(key, quote! { k })?;
+            let new = rebuild_type(v, quote! { value })?;
+            Some(quote! {{
+                let values: std::collections::BTreeMap<_, _> =
+                    std::iter::IntoIterator::into_iter(#value).map(|(k, v)| (#key, v)).collect();
+                let mut lock = (#target).lock_mut();
+                let removed: std::vec::Vec<_> = lock
+                    .keys()
+                    .filter(|k| !values.contains_key(*k))
+                    .cloned()
+                    .collect();
+                for k in removed {
+                    lock.remove(&k);
+                }
+                for (k, value) in values {
+                    if lock.contains_key(&k) {
+                        let target = &lock[&k];
+                        #element
+                    } else {
+                        lock.insert_cloned(k, #new);
+                    }
+                }
+            }})
+        }
+        _ => None,
+    })
+}
+
+/// Pushes an owned expression of the designaled type into the existing signal type at `target`.
+/// Returns `None` when there is nothing that can be updated through a shared reference.
+fn apply_type(
+    ty: &FieldType,
+    target: TokenStream,
+    value: TokenStream,
+    update: Update,
+) -> Result<Option<TokenStream>> {
+    Ok(match ty {
+        FieldType::Mutable(path, inner) => {
+            match apply_type(inner, quote! { &*v }, value.clone(), update)? {
+                Some(inner) => Some(quote! {{
+                    let v = (#target).lock_ref();
+                    #inner
+                }}),
+                None => {
+                    check_compared(path, &[inner], update)?;
+                    let inner = rebuild_type(inner, value)?;
+                    match update {
+                        Update::All => Some(quote! { (#target).set(#inner); }),
+                        Update::Changed => Some(quote! { (#target).set_neq(#inner); }),
+                    }
+                }
+            }
+        }
+        FieldType::MutableVec(..)
+        | FieldType::MutableBTreeMap { .. }
+        | FieldType::MutableBTreeSet { .. } => {
+            // Nested signal types are updated rather than replaced
+            let element = match ty {
+                FieldType::MutableVec(_, inner)
+                | FieldType::MutableBTreeMap { value: inner, .. } => {
+                    apply_type(inner, quote! { target }, quote! { value }, update)?
+                }
+                _ => None,
+            };
+            match (element, update) {
+                (Some(element), _) => update_in_place(ty, target, value, element)?,
+                (None, Update::All) => {
+                    let values = rebuild_values(ty, value)?;
+                    Some(quote! { (#target).lock_mut().replace_cloned(#values); })
+                }
+                (None, Update::Changed) => {
+                    match ty {
+                        FieldType::MutableVec(path, inner) => {
+                            check_compared(path, &[inner], update)?
+                        }
+                        FieldType::MutableBTreeMap {
+                            path, key, value, ..
+                        } => check_compared(path, &[key, value], update)?,
+                        _ => (),
+                    }
+                    let values = rebuild_values(ty, value)?;
+                    Some(update_changed_values(ty, target, values))
+                }
+            }
+        }
+        FieldType::Pointer(_, inner) => apply_type(inner, quote! { &**#target }, value, update)?,
+        FieldType::Cell(path, inner) => {
+            check_compared(path, &[inner], update)?;
+            let inner = rebuild_type(inner, value)?;
+            match update {
+                Update::All => Some(quote! { (#target).set(#inner); }),
+                Update::Changed => Some(quote! {{
+                    let value = #inner;
+                    if (#target).get() != value {
+                        (#target).set(value);
+                    }
+                }}),
+            }
+        }
+        FieldType::RefCell(path, inner) => {
+            match apply_type(inner, quote! { &*v }, value.clone(), update)? {
+                Some(inner) => Some(quote! {{
+                    let v = (#target).borrow();
+                    #inner
+                }}),
+                None => {
+                    check_compared(path, &[inner], update)?;
+                    let inner = rebuild_type(inner, value)?;
+                    match update {
+                        Update::All => Some(quote! { *(#target).borrow_mut() = #inner; }),
+                        Update::Changed => Some(quote! {{
+                            let value = #inner;
+                            if *(#target).borrow() != value {
+                                *(#target).borrow_mut() = value;
+                            }
+                        }}),
+                    }
+                }
+            }
+        }
+        // These can't be set so only what is inside can be updated
+        FieldType::ReadOnlyMutable(inner) => {
+            apply_type(inner, quote! { &*v }, value, update)?.map(|inner| {
+                quote! {{
+                    let v = (#target).lock_ref();
+                    #inner
+                }}
+            })
+        }
+        FieldType::Weak(_, inner) => {
+            apply_type(inner, quote! { &*v }, value, update)?.map(|inner| {
+                quote! {
+                    if let Some(v) = (#target).upgrade() {
+                        #inner
+                    }
+                }
+            })
+        }
+        FieldType::Other {
+            renamed: Some(_), ..
+        } => {
+            let method = update.method();
+            Some(quote! { (#target).#method(#value); })
+        }
+        FieldType::Tuple(elems) => {
+            let elems = elems
+                .iter()
+                .enumerate()
+                .map(|(index, elem)| {
+                    let index = Index::from(index);
+                    apply_type(
+                        elem,
+                        quote! { &(#target).#index },
+                        quote! { value.#index },
+                        update,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let elems: Vec<_> = elems.into_iter().flatten().collect();
+            match elems.is_empty() {
+                true => None,
+                false => Some(quote! {{
+                    let value = #value;
+                    #(#elems)*
+                }}),
+            }
+        }
+        FieldType::Array(_, inner) => {
+            apply_type(inner, quote! { target }, quote! { value }, update)?.map(|inner| {
+                quote! {
+                    for (target, value) in (#target)
+                        .iter()
+                        .zip(std::iter::IntoIterator::into_iter(#value))
+                    {
+                        #inner
+                    }
+                }
+            })
+        }
+        // Only what is behind a `Mutable` can be updated through a shared reference
+        FieldType::Generic { .. }
+        | FieldType::Reference(..)
+        | FieldType::Slice(..)
+        | FieldType::Unwrapped(..)
+        | FieldType::Mapped { .. }
+        | FieldType::Cow(..)
+        | FieldType::Other { renamed: None, .. } => None,
+    })
+}
+
+/// `apply_neq` compares the rebuilt signal values, which can't be done once a nested signal type
+/// is inside another type like `Option<TasteSignal>` as it isn't `PartialEq`
+fn check_compared(path: &Path, compared: &[&FieldType], update: Update) -> Result<()> {
+    match (update, compared.iter().any(|ty| ty.is_renamed())) {
+        (Update::Changed, true) => Err(Error::new(
+            path.span(),
+            "`apply_neq` can't compare a nested type inside another type, use `apply` or `#[designal(skip_apply)]` instead",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// A removed `Cow` can't be set, nor can a `ReadOnlyMutable`, `Weak` or pointer unless there is
+/// something inside it that `apply` can update
+fn is_skipped(ty: &FieldType, update: Update) -> Result<bool> {
+    Ok(match ty {
+        FieldType::Cow(..) => true,
+        FieldType::ReadOnlyMutable(inner)
+        | FieldType::Weak(_, inner)
+        | FieldType::Pointer(_, inner) => {
+            is_skipped(inner, update)?
+                || apply_type(inner, quote! { v }, quote! { value }, update)?.is_none()
+        }
+        FieldType::Array(_, inner) => is_skipped(inner, update)?,
+        FieldType::Tuple(elems) => {
+            let elems = elems
+                .iter()
+                .map(|elem| is_skipped(elem, update))
+                .collect::<Result<Vec<_>>>()?;
+            elems.contains(&true)
+        }
+        _ => false,
+    })
+}
+
+fn apply_struct(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    fields: &[MappedField],
+    update: Update,
+) -> Result<TokenStream> {
+    let signal = &input.ident;
+    let vis = &input.vis;
+    let method = update.method();
+    let merged = generics::impl_generics(&input.generics, designal);
+    let (impl_generics, _, where_clause) = merged.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let (_, name_generics, _) = designal.split_for_impl();
+    let fields = kept_fields(fields)
+        .into_iter()
+        .map(|field| {
+            let signal_member = &field.mapped.member;
+            let member = &field.member;
+            match &field.mapped.ty {
+                Some(_) if field.mapped.atts.skip_apply.is_some() => Ok(None),
+                Some(ty) if is_skipped(ty, update)? => Err(Error::new(
+                    field.mapped.field.ty.span(),
+                    format!(
+                        "`{}` can't update this field, use `#[designal(skip_apply)]` to leave it as it is",
+                        update.method()
+                    ),
+                )),
+                Some(ty) => apply_type(
+                    ty,
+                    quote! { &self.#signal_member },
+                    quote! { value.#member },
+                    update,
+                ),
+                // Ignored fields can't be updated
+                None => Ok(None),
+            }
+        })
+        .collect_all()?;
+    let fields = fields.iter().flatten();
+    Ok(quote! {
+        impl #impl_generics #signal #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            #vis fn #method(&self, value: #name #name_generics) {
+                #(#fields)*
+            }
+        }
+    })
+}
+
+pub(crate) fn build_struct(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    fields: &[MappedField],
+    type_atts: &AttributeOptions,
+) -> Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    if type_atts.snapshot.is_some() {
+        tokens.extend(snapshot_struct(name, designal, input, fields)?);
+    }
+    if type_atts.rebuild.is_some() {
+        tokens.extend(rebuild_struct(name, designal, input, fields)?);
+    }
+    if type_atts.apply.is_some() {
+        tokens.extend(apply_struct(name, designal, input, fields, Update::All)?);
+    }
+    if type_atts.apply_neq.is_some() {
+        tokens.extend(apply_struct(
+            name,
+            designal,
+            input,
+            fields,
+            Update::Changed,
+        )?);
+    }
+    Ok(tokens)
+}
+
+pub(crate) fn build_enum(
+    name: &Ident,
+    designal: &Generics,
+    input: &DeriveInput,
+    variants: &[MappedVariant],
+    type_atts: &AttributeOptions,
+) -> Result<TokenStream> {
+    if let Some(span) = type_atts.apply.or(type_atts.apply_neq) {
+        return Err(Error::new(span, "Apply is only supported on structs"));
+    }
+    if let (Some(_), Some(span)) = (
+        type_atts.snapshot,
+        variants.iter().find_map(|variant| variant.remove),
+    ) {
+        return Err(Error::new(
+            span,
+            "A removed variant can't be converted by `snapshot`",
+        ));
+    }
+    let mut tokens = TokenStream::new();
+    if type_atts.snapshot.is_some() {
+        tokens.extend(snapshot_enum(name, designal, input, variants)?);
+    }
+    if type_atts.rebuild.is_some() {
+        tokens.extend(rebuild_enum(name, designal, input, variants)?);
+    }
+    Ok(tokens)
+}
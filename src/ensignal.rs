@@ -0,0 +1,243 @@
+//! Generates the signal struct from a plain struct: the reverse of designal
+use crate::attributes::*;
+use crate::builder::{is_std_type, make_field, rename_field, rename_type, type_vis, Naming};
+use crate::errors::{CollectAll, Errors};
+use crate::generics;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    spanned::Spanned, DataStruct, DeriveInput, Error, Field, GenericArgument, Ident, PathArguments,
+    Result, Type,
+};
+
+/// The last segment of a type path along with any type arguments eg. `Vec` & `[T]`
+fn split_type(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => {
+            let segment = p.path.segments.last()?;
+            let args = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            Some((segment.ident.to_string(), args))
+        }
+        _ => None,
+    }
+}
+
+/// The renamed type, wrapped in an `Rc` if asked for. This is `None` when the type keeps its name.
+/// A std type like `Option<T>` keeps its name and has its type arguments renamed instead
+fn renamed_type(ty: &Type, atts: &AttributeOptions, naming: Naming) -> Result<Option<TokenStream>> {
+    let renamed = match ty {
+        Type::Path(p) if p.qself.is_none() && is_std_type(&p.path) => {
+            let mut p = p.clone();
+            let mut is_renamed = false;
+            // Paths always have at least one segment
+            let segment = p.path.segments.last_mut().unwrap();
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                for arg in args.args.iter_mut() {
+                    if let GenericArgument::Type(ty) = arg {
+                        if let Some(renamed) = renamed_type(ty, atts, naming)? {
+                            *ty = syn::parse2(renamed)?;
+                            is_renamed = true;
+                        }
+                    }
+                }
+            }
+            // Any `Rc` is already around the renamed arguments
+            return Ok(match is_renamed {
+                true => Some(quote! { #p }),
+                false => None,
+            });
+        }
+        Type::Path(p) if p.qself.is_none() => {
+            let mut p = p.clone();
+            // Paths always have at least one segment
+            let segment = p.path.segments.last_mut().unwrap();
+            let name = match (atts.mapped_name(&segment.ident), &atts.renamer) {
+                (Some(name), _) => name,
+                (None, Some(renamer)) if !atts.is_excluded(&segment.ident) => {
+                    renamer.make_new_name(&segment.ident, AttributeLocation::Field(naming))?
+                }
+                _ => return Ok(None),
+            };
+            if name == segment.ident {
+                return Ok(None);
+            }
+            segment.ident = name;
+            p
+        }
+        // The container's renamers are applied to every field so skip what can't be renamed
+        _ if !is_own_renamer(atts) => return Ok(None),
+        _ => return Err(Error::new(ty.span(), "Only a named type can be renamed")),
+    };
+    Ok(Some(match atts.rc {
+        Some(_) => quote! { std::rc::Rc<#renamed> },
+        None => quote! { #renamed },
+    }))
+}
+
+/// The field has a renamer of its own rather than one given to every field by the container
+fn is_own_renamer(atts: &AttributeOptions) -> bool {
+    !matches!(
+        atts.renamer,
+        None | Some(
+            Renamer::TrimStartAll(_, _, _)
+                | Renamer::TrimEndAll(_, _, _)
+                | Renamer::AddStartAll(_, _)
+                | Renamer::AddEndAll(_, _)
+        )
+    )
+}
+
+/// Renamed types are already signal types so are left unwrapped
+fn inner_type(ty: &Type, atts: &AttributeOptions, naming: Naming) -> Result<TokenStream> {
+    Ok(renamed_type(ty, atts, naming)?.unwrap_or_else(|| quote! { #ty }))
+}
+
+/// `explicit` are the `mutable_vec` & `mutable_map` spans from the field itself. The container level
+/// options are only applied to the fields they match, but a field asking for them must match.
+fn map_type(
+    ty: &Type,
+    atts: &AttributeOptions,
+    naming: Naming,
+    explicit: (Option<Span>, Option<Span>),
+) -> Result<TokenStream> {
+    let (name, args) = split_type(ty).unwrap_or_default();
+    let is_map = name == "BTreeMap" || name == "HashMap";
+    let is_set = name == "BTreeSet" || name == "HashSet";
+    match args.as_slice() {
+        [inner] if name == "Vec" && atts.mutable_vec.is_some() => {
+            let inner = inner_type(inner, atts, naming)?;
+            Ok(quote! { futures_signals::signal_vec::MutableVec<#inner> })
+        }
+        [key, value] if is_map && atts.mutable_map.is_some() => {
+            let value = inner_type(value, atts, naming)?;
+            Ok(quote! { futures_signals::signal_map::MutableBTreeMap<#key, #value> })
+        }
+        [key] if is_set && atts.mutable_map.is_some() => {
+            Ok(quote! { futures_signals::signal_map::MutableBTreeMap<#key, ()> })
+        }
+        _ => {
+            if let (Some(span), _) = explicit {
+                Err(Error::new(span, "Use of `mutable_vec` on a non `Vec<T>`"))
+            } else if let (_, Some(span)) = explicit {
+                Err(Error::new(
+                    span,
+                    "Use of `mutable_map` on a non `BTreeMap<K, V>`, `HashMap<K, V>`, `BTreeSet<K>` or `HashSet<K>`",
+                ))
+            } else {
+                match (renamed_type(ty, atts, naming)?, ty) {
+                    // The std type itself can still change so is wrapped
+                    (Some(renamed), Type::Path(p)) if is_std_type(&p.path) => {
+                        Ok(quote! { futures_signals::signal::Mutable<#renamed> })
+                    }
+                    (Some(renamed), _) => Ok(renamed),
+                    (None, Type::Path(p)) if is_std_type(&p.path) && is_own_renamer(atts) => {
+                        Err(Error::new(
+                            atts.renamer.as_ref().map_or(p.span(), |r| *r.span()),
+                            "There is no type to rename, std types & primitives are left as they are",
+                        ))
+                    }
+                    (None, _) => Ok(quote! { futures_signals::signal::Mutable<#ty> }),
+                }
+            }
+        }
+    }
+}
+
+fn map_field(
+    field: &Field,
+    naming: Naming,
+    type_atts: &AttributeOptions,
+) -> Result<Option<TokenStream>> {
+    let atts = AttributeOptions::new(
+        &field.attrs,
+        AttributeLocation::Field(naming),
+        Derive::Ensignal,
+    )?;
+    let explicit = (atts.mutable_vec, atts.mutable_map);
+    let atts = atts.add_type_level_to_field_level(type_atts)?;
+    if atts.remove.is_some() {
+        return Ok(None);
+    }
+    let ty = if atts.ignore.is_some() {
+        let ty = &field.ty;
+        quote! { #ty }
+    } else {
+        map_type(&field.ty, &atts, naming, explicit)?
+    };
+    let ident = rename_field(field, &atts)?;
+    Ok(Some(make_field(field, ident.as_ref(), &atts, ty)))
+}
+
+fn build_struct(
+    name: Ident,
+    data: &DataStruct,
+    input: &DeriveInput,
+    type_atts: &AttributeOptions,
+) -> Result<TokenStream> {
+    let naming = Naming::of(&data.fields);
+    let vis = type_vis(input, type_atts);
+    let (ensignal_atts, replace_atts) = &type_atts.designal_attributes;
+    let fields = data
+        .fields
+        .iter()
+        .map(|field| map_field(field, naming, type_atts))
+        .collect_all()?;
+    let fields: Vec<_> = fields.into_iter().flatten().collect();
+    let generics = generics::designal_generics(&input.generics, &fields, type_atts)?;
+    let wher = &generics.where_clause;
+    let xs = vec![];
+    let current_atts = if *replace_atts {
+        &xs
+    } else {
+        &type_atts.current_attributes
+    };
+    Ok(match naming {
+        Naming::Named => quote! {
+            #(#ensignal_atts)*
+            #(#current_atts)*
+            #vis struct #name #generics
+            #wher {
+                #(#fields),*
+            }
+        },
+        Naming::Unnamed => quote! {
+            #(#ensignal_atts)*
+            #(#current_atts)*
+            #vis struct #name #generics (#(#fields),*)
+            #wher;
+        },
+    })
+}
+
+pub(crate) fn parse_input(input: DeriveInput) -> Result<TokenStream> {
+    let type_atts = AttributeOptions::new_container(&input, Derive::Ensignal)?;
+    let mut errors = Errors::default();
+    // The fields are still checked with the original name so their errors are reported too
+    let name = errors
+        .check(rename_type(&input.ident, &type_atts))
+        .unwrap_or_else(|| input.ident.clone());
+    let tokens = match &input.data {
+        syn::Data::Struct(data) => build_struct(name, data, &input, &type_atts),
+        syn::Data::Enum(_) => Err(Error::new(
+            input.ident.span(),
+            "Enums are not yet supported by Ensignal",
+        )),
+        syn::Data::Union(_) => Err(Error::new(
+            input.ident.span(),
+            "Unions are not yet supported",
+        )),
+    };
+    let tokens = errors.check(tokens);
+    errors.finish()?;
+    Ok(tokens.unwrap_or_default())
+}
@@ -0,0 +1,44 @@
+//! Collects errors so they can all be reported at once rather than one per compile
+use syn::{Error, Result};
+
+#[derive(Default)]
+pub(crate) struct Errors(Option<Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(existing) => existing.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Keeps hold of the error so the value is only returned when it's ok
+    pub(crate) fn check<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.push(e);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) trait CollectAll<T>: Iterator<Item = Result<T>> + Sized {
+    /// Like `collect::<Result<Vec<T>>>()` but carries on to combine every error
+    fn collect_all(self) -> Result<Vec<T>> {
+        let mut errors = Errors::default();
+        let values = self.filter_map(|result| errors.check(result)).collect();
+        errors.finish()?;
+        Ok(values)
+    }
+}
+
+impl<T, I: Iterator<Item = Result<T>>> CollectAll<T> for I {}
@@ -0,0 +1,222 @@
+//! Works out the generics of the generated type, which may use fewer than the original type
+use crate::attributes::AttributeOptions;
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::HashSet;
+use syn::{
+    punctuated::Punctuated, Error, GenericParam, Generics, Result, Token, TypeParamBound,
+    WherePredicate,
+};
+
+/// Every ident & lifetime in the tokens, with the lifetimes written like `'a`
+fn names(tokens: TokenStream, found: &mut HashSet<String>) {
+    let mut lifetime = false;
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => names(group.stream(), found),
+            TokenTree::Ident(ident) if lifetime => {
+                found.insert(format!("'{}", ident));
+            }
+            TokenTree::Ident(ident) => {
+                found.insert(ident.to_string());
+            }
+            TokenTree::Punct(punct) => {
+                lifetime = punct.as_char() == '\'';
+                continue;
+            }
+            TokenTree::Literal(_) => (),
+        }
+        lifetime = false;
+    }
+}
+
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Type(ty) => ty.ident.to_string(),
+        GenericParam::Lifetime(lt) => lt.lifetime.to_string(),
+        GenericParam::Const(c) => c.ident.to_string(),
+    }
+}
+
+/// Only keeps the params that are still used by the fields, along with any their bounds
+/// & where predicates need
+fn prune(generics: &Generics, fields: &[TokenStream]) -> Generics {
+    let params: HashSet<String> = generics.params.iter().map(param_name).collect();
+    let mut used = HashSet::new();
+    for field in fields {
+        names(field.clone(), &mut used);
+    }
+    used.retain(|name| params.contains(name));
+    let predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|wher| wher.predicates.iter());
+    // A param used in a bound of a used param must also be kept
+    loop {
+        let mut found = used.clone();
+        for param in &generics.params {
+            if used.contains(&param_name(param)) {
+                names(quote::quote! { #param }, &mut found);
+            }
+        }
+        for predicate in predicates.clone() {
+            let mut mentions = HashSet::new();
+            names(quote::quote! { #predicate }, &mut mentions);
+            if mentions.iter().any(|name| used.contains(name)) {
+                found.extend(mentions);
+            }
+        }
+        found.retain(|name| params.contains(name));
+        if found.len() == used.len() {
+            break;
+        }
+        used = found;
+    }
+
+    let mut pruned = generics.clone();
+    pruned.params = generics
+        .params
+        .iter()
+        .filter(|param| used.contains(&param_name(param)))
+        .cloned()
+        .collect();
+    if let Some(wher) = &mut pruned.where_clause {
+        wher.predicates = predicates
+            .filter(|predicate| {
+                let mut mentions = HashSet::new();
+                names(quote::quote! { #predicate }, &mut mentions);
+                mentions.retain(|name| params.contains(name));
+                mentions.is_empty() || mentions.iter().any(|name| used.contains(name))
+            })
+            .cloned()
+            .collect();
+        if wher.predicates.is_empty() {
+            pruned.where_clause = None;
+        }
+    }
+    if pruned.params.is_empty() {
+        pruned.lt_token = None;
+        pruned.gt_token = None;
+    }
+    pruned
+}
+
+/// Removes any trait bounds named in `remove_bound`, marking the names that were found
+fn remove_bounds(
+    bounds: &mut Punctuated<TypeParamBound, Token![+]>,
+    atts: &AttributeOptions,
+    removed: &mut HashSet<String>,
+) {
+    *bounds = bounds
+        .iter()
+        .filter(|bound| match bound {
+            TypeParamBound::Trait(t) => match t.path.segments.last() {
+                Some(segment) => match atts
+                    .remove_bound
+                    .iter()
+                    .find(|(name, _)| segment.ident == name)
+                {
+                    Some((name, _)) => {
+                        removed.insert(name.clone());
+                        false
+                    }
+                    None => true,
+                },
+                None => true,
+            },
+            TypeParamBound::Lifetime(_) => true,
+        })
+        .cloned()
+        .collect();
+}
+
+/// The `generics` option is used as it is, otherwise any the fields no longer use are removed.
+/// Then any `remove_bound` traits are taken off & the `bound` predicates are added.
+pub(crate) fn designal_generics(
+    generics: &Generics,
+    fields: &[TokenStream],
+    atts: &AttributeOptions,
+) -> Result<Generics> {
+    let mut designal = match &atts.generics {
+        Some((generics, _)) => generics.clone(),
+        None => prune(generics, fields),
+    };
+    let mut removed = HashSet::new();
+    for param in designal.type_params_mut() {
+        remove_bounds(&mut param.bounds, atts, &mut removed);
+        if param.bounds.is_empty() {
+            param.colon_token = None;
+        }
+    }
+    if let Some(wher) = &mut designal.where_clause {
+        for predicate in wher.predicates.iter_mut() {
+            if let WherePredicate::Type(predicate) = predicate {
+                remove_bounds(&mut predicate.bounds, atts, &mut removed);
+            }
+        }
+        wher.predicates = wher
+            .predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Type(predicate) => !predicate.bounds.is_empty(),
+                _ => true,
+            })
+            .cloned()
+            .collect();
+    }
+    // A bound on a param that was removed with its fields is also gone, so isn't an error
+    let mut used = HashSet::new();
+    names(quote::quote! { #generics }, &mut used);
+    if let Some((name, span)) = atts
+        .remove_bound
+        .iter()
+        .find(|(name, _)| !removed.contains(name) && !used.contains(name))
+    {
+        return Err(Error::new(
+            *span,
+            format!("There is no bound `{}` to remove", name),
+        ));
+    }
+    if !atts.bound.is_empty() {
+        designal
+            .make_where_clause()
+            .predicates
+            .extend(atts.bound.iter().cloned());
+    }
+    if let Some(wher) = &designal.where_clause {
+        if wher.predicates.is_empty() {
+            designal.where_clause = None;
+        }
+    }
+    Ok(designal)
+}
+
+/// The generics for an impl using both types: the params & bounds of the generated type are
+/// added to the original's
+pub(crate) fn impl_generics(signal: &Generics, designal: &Generics) -> Generics {
+    let mut generics = signal.clone();
+    for param in &designal.params {
+        let existing = generics
+            .params
+            .iter_mut()
+            .find(|p| param_name(p) == param_name(param));
+        match (existing, param) {
+            (Some(GenericParam::Type(existing)), GenericParam::Type(ty)) => {
+                existing.bounds.extend(ty.bounds.iter().cloned())
+            }
+            (Some(GenericParam::Lifetime(existing)), GenericParam::Lifetime(lt)) => {
+                existing.bounds.extend(lt.bounds.iter().cloned())
+            }
+            (Some(_), _) => (),
+            // Lifetimes must come before the other params
+            (None, GenericParam::Lifetime(_)) => generics.params.insert(0, param.clone()),
+            (None, param) => generics.params.push(param.clone()),
+        }
+    }
+    if let Some(wher) = &designal.where_clause {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(wher.predicates.iter().cloned());
+    }
+    generics
+}
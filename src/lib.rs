@@ -8,8 +8,18 @@
 //! - `MutableBTreeMap<K, ()>` -> `BTreeSet<K>`
 //! - `Rc<T>` -> `T`
 //! - `Arc<T>` -> `T`
+//! - `Box<T>` -> `T`, unless `T` is unsized like `dyn Fn()`, `str` or `[u8]` or refers to the type itself like `Option<Box<NodeSignal>>` in `NodeSignal`
+//! - `Cell<T>` -> `T`
+//! - `RefCell<T>` -> `T`
+//! - `ReadOnlyMutable<T>` -> `T`
+//! - `Weak<T>` -> `Option<T>`
+//! - `Cow<'a, T>` -> the owned type, so `Cow<'a, str>` becomes `String`
 //!
-//! See the [Container Attributes](#container-attributes) and [Field Attributes](#field-attributes) section for some configuration options.
+//! The wrappers are also trimmed from inside tuples, arrays, slices, references and the arguments of any other generic type, so `(Mutable<A>, Mutable<B>)` becomes `(A, B)`, `Option<Mutable<T>>` becomes `Option<T>` and `Vec<Rc<TasteSignal>>` becomes `Vec<Taste>` when renamed. The renamers skip primitives & std types like `String`, `Option`, `Vec`, `Box` & `HashMap` and rename their arguments instead. `rename`, `add_start`, `add_end` & `rename_all` only rename the one other type in the field, so `HashMap<String, TasteSignal>` with `rename = "Taste"` becomes `HashMap<String, Taste>`, as does `(TasteSignal, u8)` to `(Taste, u8)`, and it is an error if there is no such type or more than one. The trims & replaces rename every type they are found in at any depth, so `trim_end_all = "Signal"` renames `PairSignal<Option<TasteSignal>>` to `Pair<Option<Taste>>`. Any module path is kept, so `flavour::TasteSignal` becomes `flavour::Taste`. `snapshot` & `rebuild` can only convert the arguments of these std types and `apply` leaves them as they are.
+//!
+//! A wrapper written as just its name like `Rc<T>` is always removed, but a longer path must lead to where it is defined like `std::rc::Rc<T>` or `futures_signals::signal::Mutable<T>`. Your own type with the same name can be kept by using its path like `crate::Mutable`.
+//!
+//! See the [Container Attributes](#container-attributes), [Variant Attributes](#variant-attributes) and [Field Attributes](#field-attributes) section for some configuration options.
 //!
 //! ```
 //! use designal::Designal;
@@ -66,31 +76,95 @@
 //! ```
 //!
 //! ## Container Attributes
-//! Every struct will need to have one of the renaming attributes `rename`, `add_start`, `add_end`, `trim_start`, `trim_start_all`, `trim_end`, or `trim_end_all`.
+//! Every struct will need to have one of the renaming attributes `rename`, `add_start`, `add_end`, `add_start_all`, `add_end_all`, `trim_start`, `trim_start_all`, `trim_end`, `trim_end_all`, `trim_start_repeat`, `trim_end_repeat`, `rename_all` or `replace`. It is an error for a renamer to leave a name empty or one that isn't a valid identifier. The options can be mixed in a single attribute like `#[designal(trim_end = "Signal", attribute = #[derive(Debug)], keep_rc)]` or spread over several.
 //! #### `#[designal(rename = "NewName")]`
 //! Renames the struct completely.
 //! #### `#[designal(add_start = "Prefix")]`
 //! Renames the struct by adding the string to the start of the struct identifier.
 //! #### `#[designal(add_end = "Postfix")]`
 //! Renames the struct by adding the string to the end of the struct identifier.
+//! #### `#[designal(add_start_all = "Prefix", for = "TasteSignal, FlavoursSignal")]`
+//! Renames the struct by adding the string to the start of the struct identifier and also renames the types listed in `for` wherever they are in the fields. If the field has its own renamer that will take precedence.
+//! #### `#[designal(add_end_all = "Postfix", for = "TasteSignal, FlavoursSignal")]`
+//! Renames the struct by adding the string to the end of the struct identifier and also renames the types listed in `for` wherever they are in the fields. If the field has its own renamer that will take precedence.
 //! #### `#[designal(trim_start = "Prefix")]`
-//! Renames the struct by removing the string from the start of the struct identifier.
+//! Renames the struct by removing the string once from the start of the struct identifier.
+//! #### `#[designal(trim_start_repeat = "Prefix")]`
+//! Renames the struct by removing every repeat of the string from the start of the struct identifier, so `trim_start_repeat = "Signal"` renames `SignalSignalState` to `State`.
 //! #### `#[designal(trim_start_all = "Prefix")]`
 //! Renames the struct by removing the string from the start of the struct identifier and also renames any field types that start with the same prefix. If a field doesn't start with the prefix it is left as is; if the field has its own renamer that will take precedence.
 //! #### `#[designal(trim_end = "Postfix")]`
-//! Renames the struct by removing the string from the end of the struct identifier.
+//! Renames the struct by removing the string once from the end of the struct identifier, so `trim_end = "Signal"` renames `StateSignalSignal` to `StateSignal`.
+//! #### `#[designal(trim_end_repeat = "Postfix")]`
+//! Renames the struct by removing every repeat of the string from the end of the struct identifier, so `trim_end_repeat = "Signal"` renames `StateSignalSignal` to `State`.
 //! #### `#[designal(trim_end_all = "Postfix")]`
 //! Renames the struct by removing the string from the end of the struct identifier and also renames any field types that end with the same postfix. If a field doesn't end with the postfix it is left as is; if the field has its own renamer that will take precedence.
+//! #### `#[designal(rename_all = "PascalCase")]`
+//! Renames the struct by converting it to another case. One of `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `lowercase` or `UPPERCASE`.
+//! #### `#[designal(replace = "Signal" => "Dto")]`
+//! Renames the struct by replacing every occurrence of the string with another. Starting it with `^` only replaces it at the start & ending it with `$` only at the end, like `replace = "Signal$" => "Dto"`.
+//! #### `#[designal(rename_exclude = "SignalConfig, Settings")]`
+//! The listed types keep their names in every field, for types that happen to match a renamer like `trim_end_all = "Signal"`.
+//! #### `#[designal(rename_map(TasteSignal = "Taste", FlavoursSignal = "Flavours"))]`
+//! Gives the listed types new names wherever they are in the fields. This takes precedence over any renamer.
+//! #### `#[designal(add_field_start = "prefix_")]`
+//! Renames every named field by adding the string to the start of its name. If the field has its own field renamer that will take precedence.
+//! #### `#[designal(add_field_end = "_postfix")]`
+//! Renames every named field by adding the string to the end of its name. If the field has its own field renamer that will take precedence.
+//! #### `#[designal(trim_field_start = "prefix_")]`
+//! Renames any named field that starts with the string by removing it from the start of its name, so `signal_name` becomes `name`. Other fields are left as is; if the field has its own field renamer that will take precedence.
+//! #### `#[designal(trim_field_end = "_postfix")]`
+//! Renames any named field that ends with the string by removing it from the end of its name, so `name_signal` becomes `name`. Other fields are left as is; if the field has its own field renamer that will take precedence.
+//! #### `#[designal(field_case = "camelCase")]`
+//! Renames every named field by converting it to another case, using the same cases as `rename_all`. If the field has its own field renamer that will take precedence.
 // #### `#[designal(derive = "Debug")]`
 // Adds a derive attribute to the generated struct. Can accept a list of csv values `#[designal(derive = "Serialize, Deserialize, Debug, Default")]`; be used multiple times; or like`#[designal(derive = "Debug", derive = "PartialEq")]`.
 //! #### `#[designal(cfg_feature = "your_feature")]`
 //! Adds a `#[cfg(feature = "your_feature")]` attribute to the generated struct.
+//! #### `#[designal(vis = "pub")]`
+//! Gives the generated type a different visibility to the signal type, like `vis = "pub(crate)"` or `vis = ""` for private.
+//! #### `#[designal(field_vis = "pub")]`
+//! Gives every field of the generated struct the visibility, so `field_vis = "pub"` makes them all public. If the field has its own `vis` that will take precedence. The fields of an enum can't be given a visibility.
+//! #### `#[designal(generics = "<T: Clone>")]`
+//! Any generic params, lifetimes & where predicates the generated type no longer uses (eg. because the only field using them was removed or a `Cow<'a, str>` became a `String`) are dropped. To use different generics give them here, including any where clause, eg. `generics = "<T> where T: Copy"`. The conversions are implemented using the generics of both types.
+//! #### `#[designal(bound = "T: Serialize")]`
+//! Adds where predicates to the generated type, which can be several like `bound = "T: Serialize, U: Clone"`. They are also needed by any conversions.
+//! #### `#[designal(remove_bound = "SignalItem")]`
+//! Takes the traits off the generic params & where clause of the generated type, like `remove_bound = "SignalItem, Clone"`. Use it with `bound` to replace a bound. Any types in the defaults, bounds & where clause are renamed like the types of the fields eg. `U: From<TasteSignal>` -> `U: From<Taste>` with `trim_end_all = "Signal"`.
+//! #### `#[designal(repr = "u8")]`
+//! Gives the generated type `#[repr(u8)]` in place of any `#[repr(..)]` on the signal type, which is otherwise kept like the other attributes. The discriminants of an enum like `Sweet = 1` are always kept.
 //! #### `#[designal(keep_rc)]`
 //! Keeps any `Rc`'s used on any fields.
 //! #### `#[designal(keep_arc)]`
 //! Keeps any `Arc`'s used on any fields.
+//! #### `#[designal(keep_box)]`
+//! Keeps any `Box`'s used on any fields.
+//! #### `#[designal(keep_cell)]`
+//! Keeps any `Cell`'s used on any fields.
+//! #### `#[designal(keep_refcell)]`
+//! Keeps any `RefCell`'s used on any fields.
+//! #### `#[designal(keep_weak)]`
+//! Keeps any `Weak`'s used on any fields.
+//! #### `#[designal(keep_cow)]`
+//! Keeps any `Cow`'s used on any fields.
+//! #### `#[designal(keep_read_only)]`
+//! Keeps any `ReadOnlyMutable`'s used on any fields.
 //! #### `#[designal(hashmap)]`
 //! If any field is a `MutableBTreeMap<K, V>` returns it as a `HashMap<K, V>` rather than the default of `BTreeMap<K, V>`. If any field is `MutableBTreeMap<K, ()>` returns it as a `HashSet<K>`.
+//! #### `#[designal(unwrap = "Validated")]`
+//! Removes your own wrapper type from every field, like `Mutable`, so `Validated<T>` becomes `T`. Can be used multiple times. Fields using it can't be converted with `snapshot` or `rebuild`.
+//! #### `#[designal(map = "MyVec => Vec")]`
+//! Replaces your own type in every field with another, so `MyVec<T>` becomes `Vec<T>`. Can be used multiple times. `snapshot` & `rebuild` convert between the two using `From<&MyVec<T>> for Vec<T>` & `From<Vec<T>> for MyVec<T>`.
+//! #### `#[designal(alias(M = "Mutable"))]`
+//! Removes the wrapper when it is used under another name, like `use futures_signals::signal::Mutable as M;`. Any of the wrappers above can be given an alias.
+//! #### `#[designal(snapshot)]`
+//! Also generates `impl From<&HumanSignal> for Human` which reads the current value out of every `Mutable`, `MutableVec` and `MutableBTreeMap`. Any field type that has been renamed is converted using its own `From<&T>` implementation, so nested types should also use `snapshot`. Fields that are left as is are cloned.
+//! #### `#[designal(rebuild)]`
+//! Also generates `impl From<Human> for HumanSignal` which wraps every value back up in its `Mutable`, `MutableVec`, `MutableBTreeMap`, `Rc` or `Arc`. Any field type that has been renamed is converted using its own `From<T>` implementation, so nested types should also use `rebuild`. Removed fields are set using `Default::default()` unless they have a `default` attribute. A `Cow` is rebuilt as `Cow::Owned` and a `Weak` can't be rebuilt as nothing would own its value.
+//! #### `#[designal(apply)]`
+//! Also generates `HumanSignal::apply(&self, value: Human)` which pushes the values into the existing signal struct so any subscribers are kept: every `Mutable` is `set` and every `MutableVec` and `MutableBTreeMap` has its values replaced. Any field type that has been renamed is updated in place by calling its own `apply`, so nested types should also use `apply`. This includes the elements of a `MutableVec` and the values of a `MutableBTreeMap`, which keep their `Mutable`s; any new ones are added with `rebuild` so the nested type must also use `rebuild` and be `Clone`. Removed and ignored fields, and any fields that are not wrapped in a `Mutable`, are left unchanged. It is an error to use it with a `Cow`, or a `ReadOnlyMutable`, `Weak`, `Rc`, `Arc` or `Box` without a `Mutable` or nested type inside it, since its value can't be set; give those fields `skip_apply`. Only structs are supported.
+//! #### `#[designal(apply_neq)]`
+//! Same as `apply` but generates `HumanSignal::apply_neq(&self, value: Human)` which only notifies subscribers of the values that have changed: every `Mutable` uses `set_neq`, a `MutableVec` is updated by position and a `MutableBTreeMap` by key. The values must implement `PartialEq`, except nested designaled types which are updated in place with their own `apply_neq`. A nested type inside another type, like `Mutable<Option<TasteSignal>>`, can't be compared so is an error; use `apply` or give the field `skip_apply`.
 //!
 //! ## Field Attributes
 //! #### `#[designal(rename = "NewName")]`
@@ -100,24 +174,100 @@
 //! #### `#[designal(add_end = "Postfix")]`
 //! Renames the field's declared type by adding the string to the end of the field's declared type identifier.
 //! #### `#[designal(trim_start = "Prefix")]`
-//! Renames the field's declared type by removing the string from the start of the field's declared type identifier.
+//! Renames every type in the field that starts with the string by removing it once from the start, so `(TasteSignal, u8)` becomes `(Taste, u8)`. At least one of the types must start with it.
+//! #### `#[designal(trim_start_repeat = "Prefix")]`
+//! Renames every type in the field that starts with the string by removing every repeat of it from the start.
 //! #### `#[designal(trim_end = "Postfix")]`
-//! Renames the field's declared type by removing the string from the end of the field's declared type identifier.
+//! Renames every type in the field that ends with the string by removing it once from the end, so `(TasteSignal, u8)` becomes `(Taste, u8)`. At least one of the types must end with it.
+//! #### `#[designal(trim_end_repeat = "Postfix")]`
+//! Renames every type in the field that ends with the string by removing every repeat of it from the end.
+//! #### `#[designal(rename_all = "PascalCase")]`
+//! Renames the field's declared type by converting it to another case.
+//! #### `#[designal(replace = "Signal" => "Dto")]`
+//! Renames every type in the field that contains the string by replacing it with another. At least one of the types must contain it.
+//! #### `#[designal(rename_exclude = "SignalConfig")]`
+//! The listed types keep their names in this field. Any listed at the container level are also kept.
+//! #### `#[designal(rename_map(TasteSignal = "Taste"))]`
+//! Gives the listed types new names wherever they are in this field. Any given at the container level are also used unless the field names the same type.
+//! #### `#[designal(nested)]`
+//! Renames the field's types the same way as the struct, for fields holding other designaled types. With `#[designal(trim_end = "Signal")]` on the struct `Vec<TasteSignal>` becomes `Vec<Taste>`, while with `add_start_all` or `add_end_all` only the types in its `for` are renamed. It is an error if none of the field's types match, and a struct using `rename` can't be used for its fields.
+//! #### `#[designal(field_name = "new_name")]`
+//! Renames the field itself rather than its type. Only one of `field_name`, `field_case`, `add_field_start`, `add_field_end`, `trim_field_start` & `trim_field_end` can be used on a field.
+//! #### `#[designal(field_case = "camelCase")]`
+//! Renames the field by converting it to another case.
+//! #### `#[designal(add_field_start = "prefix_")]`
+//! Renames the field by adding the string to the start of its name.
+//! #### `#[designal(add_field_end = "_postfix")]`
+//! Renames the field by adding the string to the end of its name.
+//! #### `#[designal(trim_field_start = "prefix_")]`
+//! Renames the field by removing the string from the start of its name.
+//! #### `#[designal(trim_field_end = "_postfix")]`
+//! Renames the field by removing the string from the end of its name.
 //! #### `#[designal(remove)]`
-//! Removes the field from the generated struct.
+//! Removes the field from the generated struct. The fields of a tuple struct or tuple variant are renumbered, so removing the middle field of `Meal(A, B, C)` leaves `Meal(A, C)` and any patterns must match the new positions.
+//! #### `#[designal(default = "path")]`
+//! Only valid on a removed field. The function at `path` is called to create the field's value when using `rebuild`.
 //! #### `#[designal(ignore)]`
 //! Tells `designal` to leave the field alone and return it as is.
+//! #### `#[designal(skip_apply)]`
+//! Leaves the field unchanged in `apply` & `apply_neq`, for the fields they can't update like a `Cow` or `ReadOnlyMutable<T>`.
 //! #### `#[designal(keep_rc)]`
 //! Keeps any `Rc`'s used in the field.
 //! #### `#[designal(keep_arc)]`
 //! Keeps any `Arc`'s used in the field.
+//! #### `#[designal(keep_box)]`
+//! Keeps any `Box`'s used in the field.
+//! #### `#[designal(keep_cell)]`
+//! Keeps any `Cell`'s used in the field.
+//! #### `#[designal(keep_refcell)]`
+//! Keeps any `RefCell`'s used in the field.
+//! #### `#[designal(keep_weak)]`
+//! Keeps any `Weak`'s used in the field.
+//! #### `#[designal(keep_cow)]`
+//! Keeps any `Cow`'s used in the field.
+//! #### `#[designal(keep_read_only)]`
+//! Keeps any `ReadOnlyMutable`'s used in the field.
 //! #### `#[designal(hashmap)]`
 //! If the field is a `MutableBTreeMap<K, V>` returns it as a `HashMap<K, V>` rather than the default of `BTreeMap<K, V>`. If it is `MutableBTreeMap<K, ()>` returns it as a `HashSet<K>`.
+//! #### `#[designal(unwrap = "Validated")]`
+//! Removes your own wrapper type from the field. This is added to any used on the container.
+//! #### `#[designal(map = "MyVec => Vec")]`
+//! Replaces your own type in the field with another. This is used instead of any for the same type on the container.
+//! #### `#[designal(alias(M = "Mutable"))]`
+//! Removes the wrapper from the field when it is used under another name. This is added to any used on the container.
+//! #### `#[designal(vis = "pub")]`
+//! Gives the generated field a different visibility to the signal field.
+//!
+//! ## Variant Attributes
+//! The variants of an enum can have some options of their own. Any that change the types are only applied to the fields of the variant, along with those on the container. The fields of a tuple variant follow the same rules as a tuple struct, so they can't have their own renamer or a new name.
+//! #### `#[designal(remove)]`
+//! Removes the variant from the generated enum. It can't be used with `snapshot` since there would be nothing to convert the variant to.
+//! #### `#[designal(rename = "NewName")]`
+//! Renames the variant, as do `add_start`, `add_end`, `trim_start`, `trim_end`, `trim_start_repeat`, `trim_end_repeat`, `rename_all` & `replace`. The conversions use the new name.
+//! #### `#[designal(attribute = #[..attribute..])]`
+//! Adds an attribute to the variant, `attribute_replace` also replaces any others it has.
+//! #### `#[designal(keep_rc)]`
+//! The `keep_*` options, `hashmap`, `unwrap`, `map`, `alias`, `rename_exclude`, `rename_map` & the field renamers like `field_case` are used for every field of the variant.
+//!
+//! ## Ensignal
+//! `#[derive(Ensignal)]` works the other way around: it takes a plain struct and generates the signal struct, using the `#[ensignal(..)]` attribute. The struct must be renamed in the same way as with `designal` and `attribute`, `attribute_replace`, `remove`, `ignore`, `vis`, `field_vis`, `generics`, `bound`, `remove_bound` & the field renamers like `field_name` work the same. Each field is wrapped in a `Mutable<T>` unless told otherwise.
+//! #### `#[ensignal(mutable_vec)]`
+//! Turns a `Vec<T>` into a `MutableVec<T>` rather than a `Mutable<Vec<T>>`. At the container level it applies to every `Vec<T>`.
+//! #### `#[ensignal(mutable_map)]`
+//! Turns a `BTreeMap<K, V>` or `HashMap<K, V>` into a `MutableBTreeMap<K, V>` and a `BTreeSet<K>` or `HashSet<K>` into a `MutableBTreeMap<K, ()>`. At the container level it applies to every map & set.
+//! #### `#[ensignal(rename = "NewName")]`
+//! As well as `add_start`, `add_end`, `trim_start` & `trim_end` renames the field's declared type, or the values of a `mutable_vec` or `mutable_map`. A renamed type is taken to already be a signal type so is not wrapped in a `Mutable<T>`. Primitives & std types keep their names and have their type arguments renamed instead, so `Option<Taste>` or a `Vec<Taste>` without `mutable_vec` becomes `Mutable<Option<TasteSignal>>` or `Mutable<Vec<TasteSignal>>`.
+//! #### `#[ensignal(rc)]`
+//! Wraps the renamed type in an `Rc<T>`. At the container level it applies to every renamed type.
 
 mod attribute_parser;
 mod attributes;
 mod builder;
 mod capture;
+mod convert;
+mod ensignal;
+mod errors;
+mod generics;
 use std::{
     fs::File,
     io::{Read, Write},
@@ -134,6 +284,15 @@ pub fn designal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     tokens.into()
 }
 
+#[proc_macro_derive(Ensignal, attributes(ensignal))]
+pub fn ensignal(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let tokens = ensignal::parse_input(input).unwrap_or_else(|err| err.to_compile_error());
+    let action = |mut file: File| file.write_all(tokens.to_string().as_bytes()).unwrap();
+    capture::edit_file(action);
+    tokens.into()
+}
+
 // This is an experimental feature to enabling writing the output to a file since can't know order of compilation
 // OUT_DIR not set, but CARGO_MANIFEST_DIR is. `include!()` could be used to load generated files...
 // It looks like modules are processed by the order of import
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use designal::Designal;
+use designal::{Designal, Ensignal};
 use futures_signals::signal::Mutable;
 use futures_signals::signal_map::MutableBTreeMap;
 use futures_signals::signal_vec::MutableVec;
@@ -86,6 +86,32 @@ fn trim_end_struct() {
     let _ = Human();
 }
 
+fn trim_once() {
+    struct TasteSignalSignal;
+    struct TasteSignal;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct BeanSignalSignal {
+        #[designal(trim_end = "Signal")]
+        taste: TasteSignalSignal,
+    }
+    let _ = BeanSignal { taste: TasteSignal };
+}
+
+fn trim_repeat() {
+    struct SignalSignalTaste;
+    struct Taste;
+
+    #[derive(Designal)]
+    #[designal(trim_end_repeat = "a")]
+    struct Boaaa {
+        #[designal(trim_start_repeat = "Signal")]
+        taste: SignalSignalTaste,
+    }
+    let _ = Bo { taste: Taste };
+}
+
 fn trim_start_struct_all() {
     struct HumanTaste;
     struct Taste;
@@ -141,6 +167,129 @@ fn trim_end_struct_all_rename_field() {
     };
 }
 
+fn trim_nested_types() {
+    mod flavour {
+        pub struct TasteSignal;
+        pub struct Taste;
+    }
+    struct TasteSignal;
+    struct Taste;
+    struct SignalConfig;
+    struct PairSignal<T>(T);
+    struct Pair<T>(T);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", rename_exclude = "SignalConfig")]
+    struct HumanSignal {
+        tastes: Vec<Option<Mutable<TasteSignal>>>,
+        pair: PairSignal<Option<TasteSignal>>,
+        flavour: Option<flavour::TasteSignal>,
+        config: Option<SignalConfig>,
+        #[designal(trim_end = "Signal")]
+        scores: (TasteSignal, u8, Rc<SignalConfig>),
+    }
+    let _ = Human {
+        tastes: vec![Some(Taste)],
+        pair: Pair(Some(Taste)),
+        flavour: Some(flavour::Taste),
+        config: Some(SignalConfig),
+        scores: (Taste, 1, SignalConfig),
+    };
+}
+
+fn add_end_all_for() {
+    struct TasteSignal;
+    struct TasteSignalDto;
+    struct Flavour;
+    struct FlavourDto;
+    struct Config;
+
+    #[derive(Designal)]
+    #[designal(add_end_all = "Dto", for = "TasteSignal, Flavour")]
+    struct Human {
+        taste: Vec<TasteSignal>,
+        flavour: Option<Mutable<Flavour>>,
+        config: Config,
+    }
+    let _ = HumanDto {
+        taste: vec![TasteSignalDto],
+        flavour: Some(FlavourDto),
+        config: Config,
+    };
+}
+
+fn rename_map() {
+    struct TasteSignal;
+    struct Taste;
+    struct FlavoursSignal;
+    struct Flavours;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    #[designal(rename_map(TasteSignal = "Taste", FlavoursSignal = "Flavours"))]
+    struct HumanSignal {
+        taste: Vec<TasteSignal>,
+        flavours: (FlavoursSignal, u8),
+        #[designal(rename_map(TasteSignal = "Flavours"))]
+        smell: TasteSignal,
+    }
+    let _ = Human {
+        taste: vec![Taste],
+        flavours: (Flavours, 1),
+        smell: Flavours,
+    };
+}
+
+fn nested_fields() {
+    struct TasteSignal;
+    struct Taste;
+    struct FlavourSignal;
+    struct Flavour;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct HumanSignal {
+        #[designal(nested)]
+        taste: Vec<Option<TasteSignal>>,
+        #[designal(nested)]
+        flavour: (FlavourSignal, u8),
+        name: String,
+    }
+    let _ = Human {
+        taste: vec![Some(Taste)],
+        flavour: (Flavour, 1),
+        name: String::new(),
+    };
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct PairSignal(#[designal(nested)] Mutable<TasteSignal>, u8);
+    let _ = Pair(Taste, 1);
+}
+
+fn nested_fields_for() {
+    struct TasteSignal;
+    struct TasteSignalDto;
+    struct FlavourSignal;
+
+    #[derive(Designal)]
+    #[designal(add_end_all = "Dto", for = "HumanSignal, TasteSignal")]
+    struct HumanSignal {
+        #[designal(nested)]
+        tastes: HashMap<String, TasteSignal>,
+        #[designal(nested)]
+        flavours: Option<(FlavourSignal, TasteSignal, u8)>,
+        age: Option<u8>,
+    }
+    let _ = HumanSignalDto {
+        tastes: vec![("salt".to_string(), TasteSignalDto)]
+            .into_iter()
+            .collect(),
+        flavours: Some((FlavourSignal, TasteSignalDto, 1)),
+        age: Some(8),
+    };
+}
+
 fn trim_struct_named_field() {
     #[derive(Designal)]
     #[designal(trim_end = "Bean")]
@@ -199,6 +348,67 @@ fn trim_full_path_mutable() {
     };
 }
 
+fn trim_std_paths() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", snapshot, rebuild)]
+    struct HumanBean {
+        taste: ::std::rc::Rc<futures_signals::signal::Mutable<String>>,
+        name: std::sync::Arc<String>,
+        age: std::cell::Cell<u8>,
+    }
+    let _ = Human {
+        taste: String::new(),
+        name: String::new(),
+        age: 4,
+    };
+}
+
+fn keep_other_mutable() {
+    mod other {
+        pub struct Mutable<T>(pub T);
+        pub struct Rc;
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean")]
+    struct HumanBean {
+        taste: other::Mutable<String>,
+        name: other::Rc,
+    }
+    let _ = Human {
+        taste: other::Mutable(String::new()),
+        name: other::Rc,
+    };
+}
+
+fn keep_associated_type() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean")]
+    struct HumanBean {
+        taste: <Vec<Mutable<u8>> as IntoIterator>::Item,
+    }
+    let _ = Human {
+        taste: Mutable::new(1),
+    };
+}
+
+fn trim_aliased_mutable() {
+    use futures_signals::signal::Mutable as M;
+    use std::rc::Rc as Shared;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", alias(M = "Mutable"), snapshot, rebuild)]
+    struct HumanBean {
+        taste: M<String>,
+        #[designal(alias(Shared = "Rc"))]
+        name: Shared<M<String>>,
+    }
+    let _ = Human {
+        taste: String::new(),
+        name: String::new(),
+    };
+}
+
 fn trim_rc() {
     #[derive(Designal)]
     #[designal(trim_end = "Bean")]
@@ -522,6 +732,79 @@ mod upper {
     }
 }
 
+fn rename_case() {
+    #[derive(Designal)]
+    #[designal(rename_all = "PascalCase", field_case = "camelCase")]
+    #[allow(non_camel_case_types, non_snake_case)]
+    struct human_state {
+        first_name: Mutable<String>,
+        #[designal(rename_all = "PascalCase", field_case = "SCREAMING_SNAKE_CASE")]
+        taste: Mutable<taste_signal>,
+    }
+
+    #[derive(Designal)]
+    #[designal(rename_all = "PascalCase")]
+    #[allow(non_camel_case_types)]
+    struct taste_signal;
+
+    let _ = HumanState {
+        firstName: String::new(),
+        TASTE: TasteSignal(),
+    };
+}
+
+fn rename_replace() {
+    #[derive(Designal)]
+    #[designal(replace = "Signal" => "Dto")]
+    struct HumanSignal {
+        #[designal(replace = "Signal$" => "Dto")]
+        taste: Mutable<TasteSignal>,
+        #[designal(replace = "^Signal" => "")]
+        sight: Option<Rc<SignalSight>>,
+    }
+
+    #[derive(Designal)]
+    #[designal(replace = "Signal" => "Dto")]
+    struct TasteSignal;
+
+    #[derive(Designal)]
+    #[designal(replace = "^Signal" => "")]
+    struct SignalSight;
+
+    let _ = HumanDto {
+        taste: TasteDto(),
+        sight: Some(Sight()),
+    };
+}
+
+fn rename_fields() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", trim_field_end = "_signal")]
+    struct HumanSignal {
+        name_signal: Mutable<String>,
+        #[designal(field_name = "flavour")]
+        taste: Mutable<u8>,
+        #[designal(add_field_start = "favourite_")]
+        food: String,
+        age: u8,
+    }
+    let _ = Human {
+        name: String::new(),
+        flavour: 1,
+        favourite_food: String::new(),
+        age: 2,
+    };
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", add_field_end = "_count")]
+    enum CountSignal {
+        Human { legs: Mutable<u8> },
+        Giant(Mutable<u8>),
+    }
+    let _ = Count::Human { legs_count: 2 };
+    let _ = Count::Giant(3);
+}
+
 fn derive_single() {
     #[derive(Designal)]
     #[designal(trim_end = "Bean")]
@@ -586,6 +869,25 @@ fn derive_vec_attributes_csv() {
     let _ = r1 == r2;
 }
 
+fn derive_mixed_attributes() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", attribute = #[derive(Debug)], #[derive(PartialEq)], keep_rc)]
+    pub struct HumanBean {
+        #[designal(attribute = #[allow(dead_code)], trim_end = "Bean")]
+        taste: Rc<FlavourBean>,
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", attribute = #[derive(Debug, PartialEq)])]
+    pub struct FlavourBean(u8);
+
+    let r = Human {
+        taste: Rc::new(Flavour(1)),
+    };
+    println!("{:?}", r);
+    let _ = r.taste == Rc::new(Flavour(1));
+}
+
 fn derive_full_path() {
     // use find_me::FindMe;
     #[derive(designal::Designal)]
@@ -598,6 +900,28 @@ fn derive_full_path() {
     let _ = Human::default();
 }
 
+fn visibility() {
+    mod beans {
+        use designal::{Designal, Ensignal};
+
+        #[derive(Designal)]
+        #[designal(trim_end = "Bean", vis = "pub", field_vis = "pub")]
+        pub(crate) struct HumanBean {
+            taste: String,
+            #[designal(vis = "pub(crate)")]
+            pub(crate) age: u8,
+        }
+
+        #[derive(Ensignal)]
+        #[ensignal(trim_end = "Dto", vis = "pub", field_vis = "pub")]
+        struct GiantDto {
+            height: u8,
+        }
+    }
+    let _ = |h: beans::Human| (h.taste, h.age);
+    let _ = |g: beans::Giant| g.height;
+}
+
 fn generics() {
     #[derive(Designal)]
     #[designal(trim_end = "Bean")]
@@ -621,6 +945,93 @@ fn generics_unnamed() {
     let _ = Human(&5);
 }
 
+fn generics_removed() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean")]
+    struct HumanBean<'a, T, U: Into<T>>
+    where
+        T: Copy,
+        U: Clone,
+    {
+        taste: T,
+        #[designal(remove)]
+        friend: &'a U,
+    }
+
+    let _: Human<u8> = Human { taste: 5 };
+}
+
+fn generics_cow() {
+    use std::borrow::Cow;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean")]
+    struct HumanBean<'a> {
+        name: Cow<'a, str>,
+    }
+
+    let _: Human = Human {
+        name: "Sophie".to_string(),
+    };
+}
+
+fn generics_override() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", generics = "<T> where T: Copy")]
+    struct HumanBean<T: Clone> {
+        taste: T,
+    }
+
+    let _: Human<u8> = Human { taste: 5 };
+}
+
+fn generics_bounds() {
+    trait SignalItem {}
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    #[designal(attribute = #[derive(Clone)])]
+    struct ItemSignal(Mutable<u8>);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", snapshot)]
+    #[designal(remove_bound = "SignalItem", bound = "T: Default, U: Clone")]
+    struct ListSignal<T: SignalItem + Clone, U = ItemSignal>
+    where
+        T: SignalItem,
+        U: From<ItemSignal>,
+    {
+        items: MutableVec<T>,
+        item: U,
+    }
+
+    let _: List<u8> = List {
+        items: vec![1],
+        item: Item(2),
+    };
+}
+
+#[test]
+fn generics_removed_conversions() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild, apply)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct HumanSignal<'a, T: Clone> {
+        age: Mutable<u8>,
+        #[designal(remove, default = "none")]
+        friend: Option<&'a T>,
+    }
+
+    fn none<'a, T>() -> Option<&'a T> {
+        None
+    }
+
+    let signal: HumanSignal<u32> = HumanSignal::from(Human { age: 5 });
+    assert_eq!(Human::from(&signal), Human { age: 5 });
+    signal.apply(Human { age: 6 });
+    assert_eq!(signal.age.get(), 6);
+}
+
 fn nested_types() {
     #[derive(Designal)]
     #[designal(trim_end = "Signal")]
@@ -662,69 +1073,881 @@ fn nested_types() {
     };
 }
 
-fn multiple_attributes() {
+fn nested_generic_arguments() {
+    struct Wrapper<T>(T);
+
     #[derive(Designal)]
-    #[designal(trim_end = "Bean")]
-    #[designal(attribute =
-        #[derive(Debug)],
-        #[derive(Clone)],
-        // #[cfg(feature = "client")]
-    )]
-    #[designal(attribute =
-        #[derive(Default)],
-    )]
-    struct HumanBean {
-        #[designal(attribute =
-            #[cfg(feature = "client")]
-        )]
-        taste: String,
-    };
+    #[designal(trim_end = "Signal")]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+    }
 
-    #[cfg(feature = "client")]
-    let _: Human = Human {
-        taste: String::new(),
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct HumanSignal<'a> {
+        #[designal(trim_end = "Signal")]
+        tastes: Vec<Rc<TasteSignal>>,
+        #[designal(trim_end = "Signal")]
+        favourite: Option<TasteSignal>,
+        age: Option<Mutable<u32>>,
+        scores: HashMap<String, Box<Mutable<u8>>>,
+        wrapped: Wrapper<Mutable<u32>>,
+        borrowed: &'a Mutable<u32>,
+        #[designal(trim_end = "Signal")]
+        friends: Option<Wrapper<TasteSignal>>,
+    }
+    let _ = Human {
+        tastes: vec![Taste { salt: 1 }],
+        favourite: Some(Taste { salt: 2 }),
+        age: Some(3),
+        scores: HashMap::new(),
+        wrapped: Wrapper(4),
+        borrowed: &5,
+        friends: Some(Wrapper(Taste { salt: 6 })),
     };
 }
 
-// TODO: Test the replacing
-designal::start_write_to_file!();
-#[test]
-fn replace_atts() {
-    #[derive(Designal, Deserialize, Serialize)]
-    #[designal(trim_end = "Bean")]
-    #[designal(attribute_replace =
-        #[derive(Debug)],
-        #[derive(Deserialize, Serialize)],
-        #[serde(rename = "RenamedBean")]
-    )]
-    #[serde(rename = "RenamedBean")]
-    struct HumanBean {
-        #[designal(attribute_replace = #[serde(rename = "designal_new_name")])]
-        #[serde(rename = "new_name")]
-        taste: String,
-    };
+fn renamed_container_arguments() {
+    struct TasteSignal;
+    struct Taste;
+    struct Flavour;
+    struct FlavourDto;
+    struct PairSignal<T>(T);
+    struct Pair<T>(T);
 
-    let json = serde_json::json! {
-        {
-            "designal_new_name": "hello"
-        }
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct HumanSignal {
+        #[designal(rename = "Taste")]
+        tastes: HashMap<String, TasteSignal>,
+        #[designal(add_end = "Dto")]
+        flavour: Option<Flavour>,
+        #[designal(rename = "Taste")]
+        favourites: Mutable<Vec<Rc<TasteSignal>>>,
+        #[designal(trim_end = "Signal")]
+        pairs: BTreeMap<u8, PairSignal<TasteSignal>>,
+        #[designal(add_end = "Dto", rename_exclude = "PairSignal")]
+        pair: PairSignal<Flavour>,
+    }
+    let _ = Human {
+        tastes: vec![("salt".to_string(), Taste)].into_iter().collect(),
+        flavour: Some(FlavourDto),
+        favourites: vec![Taste],
+        pairs: vec![(1, Pair(Taste))].into_iter().collect(),
+        pair: PairSignal(FlavourDto),
     };
-    assert!(serde_json::from_value::<Human>(json).is_ok());
 }
 
-// fn attributes_with_others() {
-//     #[derive(Designal)]
-//     #[designal(attribute = #[derive(Debug)], trim_end = "Bean")]
-//     struct HumanBean {
-//         taste: String,
-//     };
+fn tuple_array_types() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+    }
 
-//     let _: Human = Human {
-//         taste: String::new(),
-//     };
-// }
-designal::stop_write_to_file!();
-fn basic_enum_num_testing() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    #[allow(unused_parens)]
+    struct HumanSignal<'a> {
+        name: (Mutable<String>, Mutable<String>),
+        scores: [Mutable<u8>; 4],
+        ages: &'a [Mutable<u8>],
+        pair: Mutable<(Mutable<u32>, Rc<String>)>,
+        single: (Mutable<u32>,),
+        #[designal(trim_end = "Signal")]
+        tastes: ((TasteSignal), [Rc<TasteSignal>; 2]),
+    }
+    let _ = Human {
+        name: ("Sophie".to_string(), "Hopscotchy".to_string()),
+        scores: [1, 2, 3, 4],
+        ages: &[5],
+        pair: (6, "BFG".to_string()),
+        single: (7,),
+        tastes: (Taste { salt: 8 }, [Taste { salt: 9 }, Taste { salt: 10 }]),
+    };
+
+    struct Flavour;
+    struct FlavourDto;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct PairSignal {
+        #[designal(rename = "Taste")]
+        first: (TasteSignal, u8, Mutable<String>),
+        #[designal(add_end = "Dto")]
+        second: ([Flavour; 2], Option<Flavour>),
+    }
+    let _ = Pair {
+        first: (Taste { salt: 1 }, 2, "3".to_string()),
+        second: ([FlavourDto, FlavourDto], None),
+    };
+}
+
+fn custom_wrappers() {
+    struct Validated<T>(T);
+    struct Debounced<T>(T);
+    struct MyVec<T>(Vec<T>);
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", unwrap = "Validated", map = "MyVec => Vec")]
+    struct HumanSignal {
+        name: Validated<Mutable<String>>,
+        #[designal(unwrap = "Debounced")]
+        age: Debounced<Validated<u32>>,
+        tastes: MyVec<Mutable<String>>,
+        #[designal(map = "Validated => Option")]
+        nickname: Validated<String>,
+    }
+    let _ = Human {
+        name: "Sophie".to_string(),
+        age: 8,
+        tastes: vec!["frobscottle".to_string()],
+        nickname: Some("Soph".to_string()),
+    };
+}
+
+fn std_wrappers() {
+    use futures_signals::signal::ReadOnlyMutable;
+    use std::borrow::Cow;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Weak;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct HumanSignal {
+        age: Cell<u8>,
+        name: RefCell<String>,
+        taste: Box<Mutable<u32>>,
+        friend: Weak<String>,
+        nickname: Cow<'static, str>,
+        tags: Cow<'static, [u8]>,
+        height: ReadOnlyMutable<u32>,
+        #[designal(keep_box, keep_cell, keep_refcell)]
+        kept: (Box<u8>, Cell<u8>, RefCell<u8>),
+        #[designal(keep_weak, keep_cow, keep_read_only)]
+        kept_too: (Weak<u8>, Cow<'static, str>, ReadOnlyMutable<u8>),
+    }
+    let _ = Human {
+        age: 8,
+        name: "Sophie".to_string(),
+        taste: 1,
+        friend: Some("BFG".to_string()),
+        nickname: "Soph".to_string(),
+        tags: vec![1],
+        height: 2,
+        kept: (Box::new(1), Cell::new(2), RefCell::new(3)),
+        kept_too: (Weak::new(), Cow::Borrowed("4"), Mutable::new(5).read_only()),
+    };
+}
+
+#[test]
+fn boxes_kept() {
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", snapshot)]
+    struct NodeSignal {
+        value: Mutable<u8>,
+        next: Option<Box<NodeSignal>>,
+        name: Box<str>,
+        #[designal(remove)]
+        cb: Box<dyn Fn() -> u8>,
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal")]
+    struct CallbackSignal {
+        cb: Box<dyn Fn() -> u8>,
+        bytes: Box<[u8]>,
+        taste: Box<Mutable<u32>>,
+    }
+
+    let signal = NodeSignal {
+        value: Mutable::new(1),
+        next: Some(Box::new(NodeSignal {
+            value: Mutable::new(2),
+            next: None,
+            name: "tail".into(),
+            cb: Box::new(|| 2),
+        })),
+        name: "head".into(),
+        cb: Box::new(|| 1),
+    };
+    assert_eq!((signal.cb)(), 1);
+    let node = Node::from(&signal);
+    let next: Box<Node> = node.next.unwrap();
+    assert_eq!((next.value, &*next.name, node.value), (2, "tail", 1));
+    let callback = Callback {
+        cb: Box::new(|| 3),
+        bytes: Box::new([1]),
+        taste: 4,
+    };
+    assert_eq!(
+        ((callback.cb)(), callback.bytes.len(), callback.taste),
+        (3, 1, 4)
+    );
+}
+
+#[test]
+fn snapshot_struct() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct FlavoursSignal(MutableVec<String>, #[designal(remove)] Mutable<bool>, u8);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+        sour: Mutable<Rc<i8>>,
+        flavours: Rc<FlavoursSignal>,
+        scores: MutableBTreeMap<String, Arc<u32>>,
+        tags: MutableBTreeMap<u8, ()>,
+        #[designal(ignore)]
+        name: String,
+        #[designal(remove)]
+        editing: Mutable<bool>,
+    }
+
+    let signal = TasteSignal {
+        salt: Mutable::new(1),
+        sour: Mutable::new(Rc::new(2)),
+        flavours: Rc::new(FlavoursSignal(
+            MutableVec::new_with_values(vec!["strawberry".to_string()]),
+            Mutable::new(true),
+            3,
+        )),
+        scores: MutableBTreeMap::with_values(
+            vec![("a".to_string(), Arc::new(4))].into_iter().collect(),
+        ),
+        tags: MutableBTreeMap::with_values(vec![(5, ())].into_iter().collect()),
+        name: "Sophie".to_string(),
+        editing: Mutable::new(false),
+    };
+
+    assert_eq!(
+        Taste::from(&signal),
+        Taste {
+            salt: 1,
+            sour: 2,
+            flavours: Flavours(vec!["strawberry".to_string()], 3),
+            scores: vec![("a".to_string(), 4)].into_iter().collect(),
+            tags: vec![5].into_iter().collect(),
+            name: "Sophie".to_string(),
+        }
+    );
+}
+
+#[test]
+fn snapshot_generics() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean", snapshot)]
+    struct HumanBean<T>
+    where
+        T: Clone,
+    {
+        taste: Mutable<T>,
+    }
+
+    let human = HumanBean {
+        taste: Mutable::new(5),
+    };
+    assert_eq!(Human::from(&human).taste, 5);
+}
+
+#[test]
+fn rebuild_struct() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct FlavoursSignal(
+        MutableVec<String>,
+        #[designal(remove, default = "editing")] Mutable<bool>,
+        u8,
+    );
+
+    fn editing() -> Mutable<bool> {
+        Mutable::new(true)
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+        sour: Mutable<Rc<i8>>,
+        flavours: Rc<FlavoursSignal>,
+        scores: MutableBTreeMap<String, Arc<u32>>,
+        tags: MutableBTreeMap<u8, ()>,
+        #[designal(ignore)]
+        name: String,
+        #[designal(remove)]
+        editing: Mutable<bool>,
+    }
+
+    let taste = Taste {
+        salt: 1,
+        sour: 2,
+        flavours: Flavours(vec!["strawberry".to_string()], 3),
+        scores: vec![("a".to_string(), 4)].into_iter().collect(),
+        tags: vec![5].into_iter().collect(),
+        name: "Sophie".to_string(),
+    };
+    let signal = TasteSignal::from(taste.clone());
+    assert!(!signal.editing.get());
+    assert!(signal.flavours.1.get());
+    assert_eq!(Taste::from(&signal), taste);
+}
+
+#[test]
+fn apply_struct() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", apply)]
+    struct FlavoursSignal(MutableVec<String>, #[designal(remove)] Mutable<bool>, u8);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply)]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+        sour: Mutable<Rc<i8>>,
+        flavours: Rc<FlavoursSignal>,
+        meal: Mutable<Rc<FlavoursSignal>>,
+        scores: MutableBTreeMap<String, Arc<u32>>,
+        tags: MutableBTreeMap<u8, ()>,
+        #[designal(ignore)]
+        name: String,
+        #[designal(remove)]
+        editing: Mutable<bool>,
+    }
+
+    let signal = TasteSignal {
+        salt: Mutable::new(1),
+        sour: Mutable::new(Rc::new(2)),
+        flavours: Rc::new(FlavoursSignal(MutableVec::new(), Mutable::new(true), 3)),
+        meal: Mutable::new(Rc::new(FlavoursSignal(
+            MutableVec::new(),
+            Mutable::new(true),
+            3,
+        ))),
+        scores: MutableBTreeMap::new(),
+        tags: MutableBTreeMap::new(),
+        name: "Sophie".to_string(),
+        editing: Mutable::new(true),
+    };
+    let salt = signal.salt.clone();
+    let flavours = signal.flavours.clone();
+
+    signal.apply(Taste {
+        salt: 4,
+        sour: 5,
+        flavours: Flavours(vec!["strawberry".to_string()], 6),
+        meal: Flavours(vec!["frobscottle".to_string()], 6),
+        scores: vec![("a".to_string(), 7)].into_iter().collect(),
+        tags: vec![8].into_iter().collect(),
+        name: "Bloodbottler".to_string(),
+    });
+
+    assert_eq!(salt.get(), 4);
+    assert_eq!(*signal.sour.get_cloned(), 5);
+    assert_eq!(*flavours.0.lock_ref(), ["strawberry".to_string()]);
+    assert_eq!(flavours.2, 3);
+    assert_eq!(
+        *signal.meal.lock_ref().0.lock_ref(),
+        ["frobscottle".to_string()]
+    );
+    assert_eq!(*signal.scores.lock_ref().get("a").unwrap().as_ref(), 7);
+    assert!(signal.tags.lock_ref().contains_key(&8));
+    assert_eq!(signal.name, "Sophie");
+    assert!(signal.editing.get());
+}
+
+#[test]
+fn apply_nested_collections() {
+    #[derive(Designal, Clone)]
+    #[designal(trim_end = "Signal", rebuild, apply)]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply)]
+    struct MealSignal {
+        tastes: MutableVec<TasteSignal>,
+        named: MutableBTreeMap<String, TasteSignal>,
+    }
+
+    let taste = |salt| TasteSignal {
+        salt: Mutable::new(salt),
+    };
+    let signal = MealSignal {
+        tastes: MutableVec::new_with_values(vec![taste(1), taste(2)]),
+        named: MutableBTreeMap::with_values(
+            vec![("a".to_string(), taste(3)), ("b".to_string(), taste(4))]
+                .into_iter()
+                .collect(),
+        ),
+    };
+    let first = signal.tastes.lock_ref()[0].salt.clone();
+    let named = signal.named.lock_ref()[&"a".to_string()].salt.clone();
+
+    signal.apply(Meal {
+        tastes: vec![Taste { salt: 5 }],
+        named: vec![("a".to_string(), Taste { salt: 6 })]
+            .into_iter()
+            .collect(),
+    });
+    assert_eq!(first.get(), 5);
+    assert_eq!(signal.tastes.lock_ref().len(), 1);
+    assert_eq!(named.get(), 6);
+    assert!(!signal.named.lock_ref().contains_key("b"));
+
+    signal.apply(Meal {
+        tastes: vec![Taste { salt: 7 }, Taste { salt: 8 }],
+        named: vec![("c".to_string(), Taste { salt: 9 })]
+            .into_iter()
+            .collect(),
+    });
+    assert_eq!(first.get(), 7);
+    assert_eq!(signal.tastes.lock_ref()[1].salt.get(), 8);
+    assert_eq!(signal.named.lock_ref()[&"c".to_string()].salt.get(), 9);
+}
+
+#[test]
+fn apply_neq_struct() {
+    use futures::{FutureExt, StreamExt};
+    use futures_signals::signal::SignalExt;
+    use futures_signals::signal_vec::{SignalVecExt, VecDiff};
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", apply_neq)]
+    struct FlavoursSignal(MutableVec<String>);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply_neq)]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+        sweet: Mutable<bool>,
+        flavours: Rc<FlavoursSignal>,
+        scores: MutableBTreeMap<String, u32>,
+    }
+
+    let signal = TasteSignal {
+        salt: Mutable::new(1),
+        sweet: Mutable::new(true),
+        flavours: Rc::new(FlavoursSignal(MutableVec::new_with_values(vec![
+            "strawberry".to_string(),
+            "lemon".to_string(),
+            "lime".to_string(),
+        ]))),
+        scores: MutableBTreeMap::with_values(
+            vec![("a".to_string(), 1), ("b".to_string(), 2)]
+                .into_iter()
+                .collect(),
+        ),
+    };
+
+    let mut salt = signal.salt.signal().to_stream();
+    let mut sweet = signal.sweet.signal().to_stream();
+    let mut flavours = signal.flavours.0.signal_vec_cloned().to_stream();
+    assert_eq!(salt.next().now_or_never(), Some(Some(1)));
+    assert_eq!(sweet.next().now_or_never(), Some(Some(true)));
+    assert!(flavours.next().now_or_never().is_some());
+
+    signal.apply_neq(Taste {
+        salt: 1,
+        sweet: false,
+        flavours: Flavours(vec!["strawberry".to_string(), "frobscottle".to_string()]),
+        scores: vec![("a".to_string(), 1), ("c".to_string(), 3)]
+            .into_iter()
+            .collect(),
+    });
+
+    assert_eq!(salt.next().now_or_never(), None);
+    assert_eq!(sweet.next().now_or_never(), Some(Some(false)));
+    assert!(matches!(
+        flavours.next().now_or_never(),
+        Some(Some(VecDiff::UpdateAt { index: 1, .. }))
+    ));
+    assert!(matches!(
+        flavours.next().now_or_never(),
+        Some(Some(VecDiff::Pop {}))
+    ));
+    assert!(flavours.next().now_or_never().is_none());
+    assert_eq!(
+        *signal.scores.lock_ref(),
+        vec![("a".to_string(), 1), ("c".to_string(), 3)]
+            .into_iter()
+            .collect()
+    );
+}
+
+#[test]
+fn apply_neq_nested_vec() {
+    use futures::{FutureExt, StreamExt};
+    use futures_signals::signal::SignalExt;
+
+    #[derive(Designal, Clone)]
+    #[designal(trim_end = "Signal", rebuild, apply_neq)]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply_neq)]
+    struct MealSignal {
+        tastes: MutableVec<TasteSignal>,
+    }
+
+    let signal = MealSignal {
+        tastes: MutableVec::new_with_values(vec![
+            TasteSignal {
+                salt: Mutable::new(1),
+            },
+            TasteSignal {
+                salt: Mutable::new(2),
+            },
+        ]),
+    };
+    let mut first = signal.tastes.lock_ref()[0].salt.signal().to_stream();
+    let second = signal.tastes.lock_ref()[1].salt.clone();
+    assert_eq!(first.next().now_or_never(), Some(Some(1)));
+
+    signal.apply_neq(Meal {
+        tastes: vec![Taste { salt: 1 }, Taste { salt: 3 }, Taste { salt: 4 }],
+    });
+    assert_eq!(first.next().now_or_never(), None);
+    assert_eq!(second.get(), 3);
+    assert_eq!(signal.tastes.lock_ref()[2].salt.get(), 4);
+
+    signal.apply_neq(Meal {
+        tastes: vec![Taste { salt: 5 }],
+    });
+    assert_eq!(first.next().now_or_never(), Some(Some(5)));
+    assert_eq!(signal.tastes.lock_ref().len(), 1);
+}
+
+#[test]
+fn apply_nested_in_generic() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", rebuild, apply, apply_neq)]
+    struct TasteSignal {
+        salt: Mutable<u32>,
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply)]
+    struct MealSignal {
+        favourite: Mutable<Option<TasteSignal>>,
+    }
+
+    let signal = MealSignal {
+        favourite: Mutable::new(None),
+    };
+    signal.apply(Meal {
+        favourite: Some(Taste { salt: 2 }),
+    });
+    assert_eq!(signal.favourite.lock_ref().as_ref().unwrap().salt.get(), 2);
+
+    // `apply_neq` can't compare the tastes so they have to be skipped
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", apply_neq)]
+    struct DinnerSignal {
+        #[designal(skip_apply)]
+        favourite: Mutable<Option<TasteSignal>>,
+        count: Mutable<u8>,
+    }
+
+    let signal = DinnerSignal {
+        favourite: Mutable::new(None),
+        count: Mutable::new(1),
+    };
+    signal.apply_neq(Dinner {
+        favourite: Some(Taste { salt: 3 }),
+        count: 4,
+    });
+    assert!(signal.favourite.lock_ref().is_none());
+    assert_eq!(signal.count.get(), 4);
+}
+
+#[test]
+fn convert_generic_arguments() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct TasteSignal(Mutable<u32>);
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct HumanSignal {
+        #[designal(trim_end = "Signal")]
+        tastes: Vec<Rc<TasteSignal>>,
+        age: Option<Mutable<u32>>,
+        #[designal(keep_box)]
+        scores: BTreeMap<String, Box<Mutable<u8>>>,
+        name: Result<Mutable<String>, Mutable<u8>>,
+    }
+
+    let human = Human {
+        tastes: vec![Taste(1), Taste(2)],
+        age: Some(3),
+        scores: vec![("a".to_string(), Box::new(4))].into_iter().collect(),
+        name: Err(5),
+    };
+    let signal = HumanSignal::from(Human {
+        tastes: vec![Taste(1), Taste(2)],
+        age: Some(3),
+        scores: vec![("a".to_string(), Box::new(4))].into_iter().collect(),
+        name: Err(5),
+    });
+    assert_eq!(signal.tastes[1].0.get(), 2);
+    assert_eq!(signal.age.as_ref().map(|v| v.get()), Some(3));
+    assert_eq!(Human::from(&signal), human);
+}
+
+#[test]
+fn convert_tuples_arrays() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild, apply)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct TasteSignal(Mutable<u32>);
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild, apply)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct HumanSignal {
+        name: (Mutable<String>, String),
+        scores: [Mutable<u8>; 2],
+        pair: Mutable<(Mutable<u32>, Rc<u8>)>,
+        #[designal(trim_end = "Signal")]
+        tastes: [TasteSignal; 2],
+    }
+
+    let human = Human {
+        name: ("Sophie".to_string(), "Hopscotchy".to_string()),
+        scores: [1, 2],
+        pair: (3, 4),
+        tastes: [Taste(5), Taste(6)],
+    };
+    let signal = HumanSignal::from(human.clone());
+    assert_eq!(signal.name.0.get_cloned(), "Sophie");
+    assert_eq!(Human::from(&signal), human);
+
+    let pair = signal.pair.lock_ref().0.clone();
+    signal.apply(Human {
+        name: ("BFG".to_string(), "Giant".to_string()),
+        scores: [7, 8],
+        pair: (9, 10),
+        tastes: [Taste(11), Taste(12)],
+    });
+    assert_eq!(pair.get(), 9);
+    assert_eq!(
+        Human::from(&signal),
+        Human {
+            name: ("BFG".to_string(), "Hopscotchy".to_string()),
+            scores: [7, 8],
+            pair: (9, 4),
+            tastes: [Taste(11), Taste(12)],
+        }
+    );
+}
+
+#[test]
+fn convert_mapped_types() {
+    #[derive(Clone)]
+    struct MyVec<T>(Vec<T>);
+
+    impl<T: Clone> From<&MyVec<T>> for Vec<T> {
+        fn from(value: &MyVec<T>) -> Self {
+            value.0.clone()
+        }
+    }
+
+    impl<T> From<Vec<T>> for MyVec<T> {
+        fn from(value: Vec<T>) -> Self {
+            MyVec(value)
+        }
+    }
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", map = "MyVec => Vec", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct HumanSignal {
+        tastes: Mutable<MyVec<u32>>,
+    }
+
+    let signal = HumanSignal::from(Human { tastes: vec![1, 2] });
+    assert_eq!(signal.tastes.lock_ref().0, vec![1, 2]);
+    assert_eq!(Human::from(&signal), Human { tastes: vec![1, 2] });
+}
+
+#[test]
+fn convert_std_wrappers() {
+    use futures_signals::signal::ReadOnlyMutable;
+    use std::borrow::Cow;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Weak;
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild, apply_neq)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct HumanSignal {
+        age: Cell<u8>,
+        name: RefCell<String>,
+        taste: Box<Mutable<u32>>,
+        #[designal(skip_apply)]
+        nickname: Cow<'static, str>,
+        #[designal(skip_apply)]
+        height: ReadOnlyMutable<u32>,
+    }
+
+    let human = Human {
+        age: 8,
+        name: "Sophie".to_string(),
+        taste: 1,
+        nickname: "Soph".to_string(),
+        height: 2,
+    };
+    let signal = HumanSignal::from(human.clone());
+    assert_eq!(signal.name.borrow().as_str(), "Sophie");
+    assert_eq!(Human::from(&signal), human);
+
+    signal.apply_neq(Human {
+        age: 9,
+        name: "BFG".to_string(),
+        taste: 3,
+        nickname: "Giant".to_string(),
+        height: 4,
+    });
+    assert_eq!(
+        Human::from(&signal),
+        Human {
+            age: 9,
+            name: "BFG".to_string(),
+            taste: 3,
+            nickname: "Soph".to_string(),
+            height: 2,
+        }
+    );
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct FriendSignal(Weak<String>);
+
+    let name = Rc::new("BFG".to_string());
+    let friend = FriendSignal(Rc::downgrade(&name));
+    assert_eq!(Friend::from(&friend), Friend(Some("BFG".to_string())));
+    drop(name);
+    assert_eq!(Friend::from(&friend), Friend(None));
+}
+
+#[test]
+fn convert_renamed_fields() {
+    #[derive(Designal)]
+    #[designal(
+        trim_end = "Signal",
+        trim_field_end = "_signal",
+        snapshot,
+        rebuild,
+        apply
+    )]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    struct HumanSignal {
+        name_signal: Mutable<String>,
+        #[designal(field_name = "flavour")]
+        taste: Mutable<u8>,
+    }
+
+    let human = Human {
+        name: "Sophie".to_string(),
+        flavour: 1,
+    };
+    let signal = HumanSignal::from(human.clone());
+    assert_eq!(signal.taste.get(), 1);
+    assert_eq!(Human::from(&signal), human);
+    signal.apply(Human {
+        name: "BFG".to_string(),
+        flavour: 2,
+    });
+    assert_eq!(signal.name_signal.get_cloned(), "BFG");
+    assert_eq!(signal.taste.get(), 2);
+
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", add_field_start = "the_", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq, Clone)])]
+    enum SizeSignal {
+        Giant { height: Mutable<u32> },
+    }
+    let size = Size::Giant { the_height: 24 };
+    assert_eq!(Size::from(&SizeSignal::from(size.clone())), size);
+}
+
+fn multiple_attributes() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Bean")]
+    #[designal(attribute =
+        #[derive(Debug)],
+        #[derive(Clone)],
+        // #[cfg(feature = "client")]
+    )]
+    #[designal(attribute =
+        #[derive(Default)],
+    )]
+    struct HumanBean {
+        #[designal(attribute =
+            #[cfg(feature = "client")]
+        )]
+        taste: String,
+    };
+
+    #[cfg(feature = "client")]
+    let _: Human = Human {
+        taste: String::new(),
+    };
+}
+
+// TODO: Test the replacing
+designal::start_write_to_file!();
+#[test]
+fn replace_atts() {
+    #[derive(Designal, Deserialize, Serialize)]
+    #[designal(trim_end = "Bean")]
+    #[designal(attribute_replace =
+        #[derive(Debug)],
+        #[derive(Deserialize, Serialize)],
+        #[serde(rename = "RenamedBean")]
+    )]
+    #[serde(rename = "RenamedBean")]
+    struct HumanBean {
+        #[designal(attribute_replace = #[serde(rename = "designal_new_name")])]
+        #[serde(rename = "new_name")]
+        taste: String,
+    };
+
+    let json = serde_json::json! {
+        {
+            "designal_new_name": "hello"
+        }
+    };
+    assert!(serde_json::from_value::<Human>(json).is_ok());
+}
+
+#[test]
+fn attributes_with_others() {
+    #[derive(Designal)]
+    #[designal(attribute = #[derive(Debug)], trim_end = "Bean", keep_rc)]
+    struct HumanBean {
+        taste: Rc<String>,
+    }
+
+    let human = Human {
+        taste: Rc::new("salty".to_string()),
+    };
+    assert_eq!(format!("{:?}", human), r#"Human { taste: "salty" }"#);
+}
+
+designal::stop_write_to_file!();
+fn basic_enum_num_testing() {
     #[derive(Designal)]
     #[designal(trim_end = "Signal")]
     enum GiantSignal {
@@ -744,18 +1967,36 @@ fn empty_enum() {
     let _: Bean = unreachable!();
 }
 
-// #[test]
-// fn discrim_enum_num_testing() {
-//     #[derive(Designal)]
-//     #[designal(trim_end = "Signal")]
-//     enum GiantSignal {
-//         BoneCruncherSignal = 1,
-//         FleshLumpEaterSignal = 2,
-//     }
+#[test]
+fn discrim_enum_num_testing() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild)]
+    #[repr(u8)]
+    enum GiantSignal {
+        BoneCruncher = 1,
+        FleshLumpEater = 2,
+    }
 
-//     assert!(Giant::BoneCruncher as usize == 1);
-//     // assert!(Giant::FleshLumpEater as usize == 2);
-// }
+    assert!(Giant::BoneCruncher as usize == 1);
+    assert!(Giant::FleshLumpEater as usize == 2);
+    assert!(Giant::from(&GiantSignal::FleshLumpEater) as u8 == 2);
+    assert!(GiantSignal::from(Giant::BoneCruncher) as u8 == 1);
+    assert_eq!(std::mem::size_of::<Giant>(), 1);
+}
+
+#[test]
+fn discrim_enum_repr() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", repr = "u16")]
+    #[repr(u8)]
+    enum GiantSignal {
+        BoneCruncher = 1,
+        FleshLumpEater,
+    }
+
+    assert!(Giant::FleshLumpEater as u16 == 2);
+    assert_eq!(std::mem::size_of::<Giant>(), 2);
+}
 
 fn basic_enum_with_struct_field() {
     #[derive(Designal)]
@@ -782,3 +2023,244 @@ fn basic_enum_with_struct_field() {
     let _: Giant = Giant::MeatDripper(Meal(), String::new());
     let _: Giant = Giant::FleshLumpEater(Meal(), Meal());
 }
+
+#[test]
+fn snapshot_enum() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    struct MealSignal(Mutable<u32>);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    enum GiantSignal {
+        GizzardGulper {
+            name: Mutable<String>,
+            #[designal(remove)]
+            editing: Mutable<bool>,
+        },
+        BoneCruncher(Rc<MealSignal>),
+        FleshLumpEater,
+    }
+
+    let giant = GiantSignal::GizzardGulper {
+        name: Mutable::new("Gizzard".to_string()),
+        editing: Mutable::new(true),
+    };
+    assert_eq!(
+        Giant::from(&giant),
+        Giant::GizzardGulper {
+            name: "Gizzard".to_string()
+        }
+    );
+    let giant = GiantSignal::BoneCruncher(Rc::new(MealSignal(Mutable::new(3))));
+    assert_eq!(Giant::from(&giant), Giant::BoneCruncher(Meal(3)));
+    assert_eq!(
+        Giant::from(&GiantSignal::FleshLumpEater),
+        Giant::FleshLumpEater
+    );
+}
+
+#[test]
+fn rebuild_enum() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", rebuild)]
+    struct MealSignal(Mutable<u32>);
+
+    #[derive(Designal)]
+    #[designal(trim_end_all = "Signal", rebuild)]
+    enum GiantSignal {
+        GizzardGulper {
+            name: Mutable<String>,
+            #[designal(remove)]
+            editing: Mutable<bool>,
+        },
+        BoneCruncher(Rc<MealSignal>),
+        FleshLumpEater,
+    }
+
+    match GiantSignal::from(Giant::GizzardGulper {
+        name: "Gizzard".to_string(),
+    }) {
+        GiantSignal::GizzardGulper { name, editing } => {
+            assert_eq!(name.get_cloned(), "Gizzard");
+            assert!(!editing.get());
+        }
+        _ => panic!("Wrong variant"),
+    }
+    match GiantSignal::from(Giant::BoneCruncher(Meal(3))) {
+        GiantSignal::BoneCruncher(meal) => assert_eq!(meal.0.get(), 3),
+        _ => panic!("Wrong variant"),
+    }
+    assert!(matches!(
+        GiantSignal::from(Giant::FleshLumpEater),
+        GiantSignal::FleshLumpEater
+    ));
+}
+
+#[test]
+fn enum_variant_attributes() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    enum GiantSignal {
+        #[designal(trim_end = "Signal", attribute = #[allow(dead_code)])]
+        BoneCruncherSignal { meal: Mutable<u8> },
+        #[designal(keep_rc)]
+        FleshLumpEater(Rc<u8>),
+        #[designal(remove)]
+        Editing(Mutable<bool>),
+        #[designal(rename = "Butcher")]
+        TheButcherBoy,
+    }
+
+    match GiantSignal::from(Giant::BoneCruncher { meal: 2 }) {
+        GiantSignal::BoneCruncherSignal { meal } => assert_eq!(meal.get(), 2),
+        _ => panic!("Wrong variant"),
+    }
+    assert_eq!(
+        Giant::FleshLumpEater(Rc::new(3)),
+        Giant::FleshLumpEater(Rc::new(3))
+    );
+    assert!(matches!(
+        GiantSignal::from(Giant::Butcher),
+        GiantSignal::TheButcherBoy
+    ));
+}
+
+#[test]
+fn enum_tuple_variant_remove() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot, rebuild)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    enum GiantSignal {
+        Meal(
+            Mutable<u8>,
+            #[designal(remove, default = "editing")] Mutable<bool>,
+            String,
+        ),
+    }
+
+    fn editing() -> Mutable<bool> {
+        Mutable::new(true)
+    }
+
+    let signal = GiantSignal::from(Giant::Meal(1, "Bread".to_string()));
+    assert_eq!(Giant::from(&signal), Giant::Meal(1, "Bread".to_string()));
+    let GiantSignal::Meal(_, editing, _) = signal;
+    assert!(editing.get());
+}
+
+#[test]
+fn snapshot_renamed_variant() {
+    #[derive(Designal)]
+    #[designal(trim_end = "Signal", snapshot)]
+    #[designal(attribute = #[derive(Debug, PartialEq)])]
+    enum TasteSignal {
+        #[designal(trim_end = "Signal")]
+        SweetSignal(Mutable<u8>),
+        Sour,
+    }
+
+    assert_eq!(
+        Taste::from(&TasteSignal::SweetSignal(Mutable::new(1))),
+        Taste::Sweet(1)
+    );
+    assert_eq!(Taste::from(&TasteSignal::Sour), Taste::Sour);
+}
+
+fn ensignal_struct() {
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal")]
+    struct HumanBean {
+        name: String,
+        #[ensignal(mutable_vec)]
+        tastes: Vec<String>,
+        #[ensignal(mutable_map)]
+        friends: HashMap<String, u32>,
+        #[ensignal(mutable_map)]
+        foods: BTreeSet<String>,
+        #[ensignal(ignore)]
+        id: u32,
+        #[ensignal(remove)]
+        editing: bool,
+    }
+    let _ = HumanBeanSignal {
+        name: Mutable::new(String::new()),
+        tastes: MutableVec::new(),
+        friends: MutableBTreeMap::new(),
+        foods: MutableBTreeMap::<String, ()>::new(),
+        id: 0,
+    };
+}
+
+fn ensignal_unnamed_generics() {
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal", mutable_vec)]
+    struct HumanBean<T>(T, Vec<T>, Vec<u32>);
+    let _ = HumanBeanSignal(
+        Mutable::new(1),
+        MutableVec::new_with_values(vec![1]),
+        MutableVec::new(),
+    );
+}
+
+fn ensignal_renamed_fields() {
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal", add_field_end = "_signal")]
+    struct Human {
+        name: String,
+        #[ensignal(field_name = "flavour")]
+        taste: u8,
+    }
+    let _ = HumanSignal {
+        name_signal: Mutable::new(String::new()),
+        flavour: Mutable::new(1),
+    };
+}
+
+fn ensignal_nested() {
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal")]
+    #[ensignal(attribute = #[derive(Default)])]
+    struct Flavour {
+        name: String,
+    }
+
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal", mutable_map)]
+    struct HumanBean {
+        #[ensignal(add_end = "Signal")]
+        taste: Flavour,
+        #[ensignal(add_end = "Signal", rc)]
+        shared_taste: Flavour,
+        #[ensignal(add_end = "Signal", mutable_vec)]
+        tastes: Vec<Flavour>,
+        #[ensignal(add_end = "Signal")]
+        favourites: BTreeMap<u32, Flavour>,
+    }
+    let flavour = FlavourSignal::default;
+    let _ = HumanBeanSignal {
+        taste: flavour(),
+        shared_taste: Rc::new(flavour()),
+        tastes: MutableVec::new_with_values(vec![flavour()]),
+        favourites: MutableBTreeMap::<u32, FlavourSignal>::new(),
+    };
+
+    #[derive(Ensignal)]
+    #[ensignal(add_end = "Signal")]
+    struct Human {
+        #[ensignal(add_end = "Signal")]
+        favourite: Option<Flavour>,
+        #[ensignal(add_end = "Signal")]
+        tastes: Vec<Flavour>,
+        #[ensignal(add_end = "Signal", rc)]
+        scores: HashMap<String, Flavour>,
+    }
+    let _ = HumanSignal {
+        favourite: Mutable::new(Some(flavour())),
+        tastes: Mutable::new(vec![flavour()]),
+        scores: Mutable::new(HashMap::<String, Rc<FlavourSignal>>::new()),
+    };
+}
@@ -0,0 +1,61 @@
+use designal::Designal;
+use futures_signals::signal::{Mutable, ReadOnlyMutable};
+use std::borrow::Cow;
+use std::rc::Rc;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", apply)]
+struct HumanBean {
+    #[designal(apply)]
+    taste: Mutable<String>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", apply)]
+enum HumanBean1 {
+    Taste(Mutable<String>),
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", apply_neq)]
+enum HumanBean2 {
+    Taste(Mutable<String>),
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", apply)]
+struct HumanBean3 {
+    nickname: Cow<'static, str>,
+    height: ReadOnlyMutable<u32>,
+    friends: (Mutable<u8>, Rc<String>),
+    #[designal(skip_apply)]
+    skipped: Cow<'static, str>,
+    name: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", skip_apply)]
+struct HumanBean4 {
+    taste: Mutable<String>,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Signal", rebuild, apply_neq)]
+struct TasteSignal {
+    salt: Mutable<u32>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", apply_neq)]
+struct HumanBean5 {
+    #[designal(trim_end = "Signal")]
+    favourite: Mutable<Option<TasteSignal>>,
+    #[designal(trim_end = "Signal")]
+    tastes: Mutable<Vec<TasteSignal>>,
+    #[designal(trim_end = "Signal", skip_apply)]
+    skipped: Mutable<Option<TasteSignal>>,
+    #[designal(trim_end = "Signal")]
+    nested: Mutable<TasteSignal>,
+}
+
+fn main() {}
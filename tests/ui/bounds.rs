@@ -0,0 +1,16 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", remove_bound = "Clone")]
+struct HumanBean<T: Copy> {
+    taste: T,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", bound = "T Clone", remove_bound)]
+struct GiantBean<T> {
+    #[designal(bound = "T: Copy")]
+    taste: T,
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(rename_all = "kebab-case")]
+struct HumanBean;
+
+#[derive(Designal)]
+#[designal(rename_all = "PascalCase")]
+struct HumanBean1;
+
+#[derive(Designal)]
+#[designal(replace = "Signal" => "Dto")]
+struct HumanBean2;
+
+#[derive(Designal)]
+#[designal(replace = "Bean")]
+struct HumanBean3;
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean" => "Dto")]
+struct HumanBean4;
+
+#[derive(Designal)]
+#[designal(replace = "" => "Dto")]
+struct HumanBean5;
+
+#[derive(Designal)]
+#[designal(replace = "Bean" => "Dto", rename_all = "snake_case")]
+struct HumanBean6;
+
+fn main() {}
@@ -0,0 +1,56 @@
+use designal::Designal;
+use futures_signals::signal::Mutable;
+
+struct Validated<T>(T);
+struct Debounced;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", unwrap)]
+struct HumanBean {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", map = "MyVec")]
+struct HumanBean1 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", map = "MyVec => Vec => Option")]
+struct HumanBean2 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", unwrap = "Validated<T>")]
+struct HumanBean3 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", unwrap = "Validated", map = "Validated => Option")]
+struct HumanBean4 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", unwrap = "Debounced")]
+struct HumanBean5 {
+    taste: Debounced,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", unwrap = "Validated", snapshot)]
+struct HumanBean6 {
+    taste: Validated<Mutable<u32>>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean7 {
+    #[designal(ignore, unwrap = "Validated")]
+    taste: Validated<Mutable<u32>>,
+}
+
+fn main() {}
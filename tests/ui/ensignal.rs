@@ -0,0 +1,57 @@
+use designal::{Designal, Ensignal};
+use futures_signals::signal::Mutable;
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal", keep_rc)]
+struct HumanBean {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Signal", mutable_vec)]
+struct HumanBeanSignal {
+    taste: Mutable<String>,
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+struct HumanBean1 {
+    #[ensignal(mutable_vec)]
+    taste: String,
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+struct HumanBean2 {
+    #[ensignal(mutable_map)]
+    taste: Vec<String>,
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+struct HumanBean3 {
+    #[ensignal(rc)]
+    taste: String,
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+struct HumanBean4 {
+    #[ensignal(add_end = "Signal")]
+    taste: (String, String),
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+struct HumanBean5 {
+    #[ensignal(add_end = "Signal")]
+    taste: Option<String>,
+}
+
+#[derive(Ensignal)]
+#[ensignal(add_end = "Signal")]
+enum Giant {
+    BoneCruncher,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use designal::Designal;
+use futures_signals::signal::Mutable;
+
+#[derive(Designal)]
+#[designal(trim_end = "Signal", field_name = "name")]
+struct HumanSignal {
+    taste: Mutable<u8>,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Signal")]
+struct Human1Signal(#[designal(field_name = "taste")] Mutable<u8>);
+
+#[derive(Designal)]
+#[designal(trim_end = "Signal")]
+struct Human2Signal {
+    #[designal(trim_field_end = "_signal")]
+    taste: Mutable<u8>,
+    #[designal(field_name = "smell", add_field_end = "_signal")]
+    scent: Mutable<u8>,
+    #[designal(remove, field_name = "sight")]
+    see: Mutable<u8>,
+    #[designal(field_name)]
+    sound: Mutable<u8>,
+}
+
+fn main() {}
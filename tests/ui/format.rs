@@ -8,4 +8,20 @@ struct HumanBean();
 #[designal(snozzcumber)]
 struct HumanBean1();
 
+#[derive(Designal)]
+#[designal(rename = "NewBean", keep_rc = #[derive(Debug)])]
+struct HumanBean2();
+
+#[derive(Designal)]
+#[designal(rename = "NewBean", attribute = #[derive(Debug)] keep_rc)]
+struct HumanBean3();
+
+#[derive(Designal)]
+#[designal(snozzcumber, rename = "NewBean", attribute = derive(Debug))]
+struct HumanBean4();
+
+#[derive(Designal)]
+#[designal]
+struct HumanBean5();
+
 fn main() {}
\ No newline at end of file
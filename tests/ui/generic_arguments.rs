@@ -0,0 +1,49 @@
+use designal::Designal;
+use futures_signals::signal::Mutable;
+use std::collections::HashMap;
+
+struct Wrapper<T>(T);
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", snapshot)]
+struct HumanBean {
+    taste: Wrapper<Mutable<u32>>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean1<'a> {
+    taste: &'a Mutable<u32>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean2 {
+    #[designal(trim_end = "Signal")]
+    taste: Option<u32>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", snapshot)]
+struct HumanBean3<'a> {
+    taste: &'a [Mutable<u32>],
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean4 {
+    taste: Box<[Mutable<u32>]>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean5 {
+    #[designal(rename = "Taste")]
+    taste: HashMap<TasteSignal, FlavourSignal>,
+    #[designal(add_end = "Dto")]
+    age: Option<u8>,
+    #[designal(rename_all = "snake_case")]
+    names: Mutable<Vec<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", generics = "T: Clone")]
+struct HumanBean<T> {
+    taste: T,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", generics)]
+struct GiantBean<T> {
+    #[designal(generics = "<T>")]
+    taste: T,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", generics = "<T>", generics = "<T: Clone>")]
+struct FlavourBean<T> {
+    taste: T,
+}
+
+fn main() {}
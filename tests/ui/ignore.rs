@@ -44,4 +44,16 @@ struct HumanBean5 {
     taste: String,
 }
 
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean6 {
+    #[designal(ignore, ignore)]
+    taste: String,
+    #[designal(ignore, keep_rc)]
+    smell: String,
+    #[designal(ignore)]
+    #[designal(rename = "sight")]
+    see: String,
+}
+
 fn main() {}
@@ -0,0 +1,36 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+#[designal(default = "make_bean")]
+struct HumanBean();
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean1 {
+    #[designal(rebuild)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean2 {
+    #[designal(default = "make_taste")]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean3 {
+    #[designal(remove, default)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean4 {
+    #[designal(remove, default = "make taste")]
+    taste: String,
+}
+
+fn main() {}
@@ -79,4 +79,123 @@ struct Giant2Giant {
     taste: TasteHuman,
 }
 
+#[derive(Designal)]
+#[designal(rename = "", keep_rc, keep_rc)]
+struct HumanBean13 {
+    #[designal(rename = 2)]
+    taste: String,
+    #[designal(add_start)]
+    smell: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean14 {
+    #[designal(rename = "")]
+    taste: String,
+    #[designal(trim_start = 1, add_end)]
+    smell: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+struct Bean {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(rename = "Human Bean")]
+struct HumanBean15 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(add_end = "Dto")]
+struct HumanBean16 {
+    #[designal(add_start = "1")]
+    taste: Taste,
+    #[designal(trim_start_repeat = "Taste")]
+    smell: TasteTaste,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", rename_exclude = "Taste Signal")]
+struct HumanBean17 {
+    #[designal(rename_exclude)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(add_end = "Dto")]
+struct HumanBean18 {
+    #[designal(trim_end = "Signal", rename_exclude = "TasteSignal")]
+    smell: (TasteSignal, u8),
+}
+
+#[derive(Designal)]
+#[designal(add_end_all = "Dto")]
+struct HumanBean19 {
+    #[designal(add_start_all = "Plain", for = "String")]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", for = "String")]
+struct HumanBean20 {
+    #[designal(add_end_all = "Dto")]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(rename_map(TasteSignal = "Taste", TasteSignal = "Flavour"))]
+#[designal(rename_map(Smell = 1), add_end = "Dto")]
+struct HumanBean21 {
+    #[designal(rename_map)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", nested)]
+struct HumanBean22 {
+    #[designal(nested, trim_end = "Signal")]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+struct Human23Bean {
+    #[designal(nested)]
+    taste: String,
+    #[designal(nested)]
+    smell: Option<TasteBean>,
+}
+
+#[derive(Designal)]
+#[designal(rename = "Giant")]
+struct HumanBean24 {
+    #[designal(nested)]
+    taste: TasteBean,
+}
+
+struct FlavourSignal;
+struct TasteSignal;
+
+#[derive(Designal)]
+#[designal(add_end_all = "Dto", for = "HumanBean25, TasteSignal")]
+struct HumanBean25 {
+    #[designal(nested)]
+    taste: std::collections::HashMap<FlavourSignal, TasteSignal>,
+    #[designal(nested)]
+    smell: Option<FlavourSignal>,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Nope")]
+struct HumanBean26 {
+    #[designal(bogus)]
+    taste: String,
+    #[designal(keep_foo)]
+    smell: String,
+}
+
 fn main() {}
@@ -0,0 +1,22 @@
+use designal::{Designal, Ensignal};
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", repr = "")]
+enum HumanBean {
+    Sophie = 1,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", repr = "u8", repr = "u16")]
+struct GiantBean {
+    #[designal(repr = "C")]
+    taste: u8,
+}
+
+#[derive(Ensignal)]
+#[ensignal(trim_end = "Dto", repr = "C")]
+struct FlavourDto {
+    taste: u8,
+}
+
+fn main() {}
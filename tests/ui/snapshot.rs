@@ -0,0 +1,17 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", snapshot)]
+struct HumanBean {
+    #[designal(snapshot)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", snapshot)]
+#[designal(snapshot)]
+struct HumanBean1 {
+    taste: String,
+}
+
+fn main() {}
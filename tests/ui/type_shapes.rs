@@ -0,0 +1,42 @@
+use designal::Designal;
+
+mod shapes {
+    pub struct Mutable;
+    pub struct MutableBTreeMap<A, B, C>(pub A, pub B, pub C);
+    pub struct Rc<A, B>(pub A, pub B);
+    pub struct MutableVec<'a>(pub &'a str);
+}
+use shapes::*;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean {
+    taste: Mutable,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean1 {
+    taste: MutableBTreeMap<u8, u8, u8>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean2 {
+    taste: Option<Rc<u8, u8>>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean3<'a> {
+    taste: (u8, MutableVec<'a>),
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean4 {
+    #[designal(rename = "Taste")]
+    taste: (TasteSignal, u8, [FlavourSignal; 2]),
+}
+
+fn main() {}
@@ -1,6 +1,7 @@
 use designal::Designal;
 use futures_signals::signal::Mutable;
 use futures_signals::signal_vec::MutableVec;
+use std::borrow::Cow;
 
 #[derive(Designal)]
 #[designal(trim_start = "Human")]
@@ -22,4 +23,11 @@ struct HumanBean4 {
     taste: MutableVec<String>,
 }
 
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean5 {
+    #[designal(hashmap)]
+    taste: Cow<'static, str>,
+}
+
 fn main() {}
@@ -0,0 +1,61 @@
+use designal::Designal;
+use futures_signals::signal::Mutable;
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", snapshot)]
+enum HumanBean {
+    #[designal(remove)]
+    Editing(Mutable<bool>),
+    Sophie,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum GiantBean {
+    #[designal(trim_end = "Bean")]
+    BoneCruncher,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum FlavourBean {
+    #[designal(vis = "pub")]
+    Sweet,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum TasteBean {
+    #[designal(remove, rename = "Sweet")]
+    Sour,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum MealBean {
+    #[designal(trim_end_all = "Bean")]
+    BreadBean,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum DrinkBean {
+    #[designal(snapshot)]
+    Water,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", vis)]
+enum SnackBean {
+    #[designal(ignore)]
+    Crisps,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum FruitBean {
+    Apple(#[designal(trim_end = "Bean")] PipBean),
+    Pear(#[designal(field_name = "pip")] u8),
+}
+
+fn main() {}
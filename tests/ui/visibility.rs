@@ -0,0 +1,29 @@
+use designal::Designal;
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", vis = "public", field_vis)]
+struct HumanBean {
+    #[designal(field_vis = "pub")]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", vis = "pub", vis = "pub(crate)")]
+struct GiantBean {
+    #[designal(vis = 1)]
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean")]
+enum FlavourBean {
+    Sweet(#[designal(vis = "pub")] String),
+}
+
+#[derive(Designal)]
+#[designal(trim_end = "Bean", field_vis = "pub")]
+enum TasteBean {
+    Sweet { sugar: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+use designal::{Designal, Ensignal};
+
+struct Mutable;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean {
+    taste: Mutable,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", alias(M = "Mutabl"))]
+struct HumanBean1 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", alias(M = "Mutable"), alias(M = "Rc"))]
+struct HumanBean2 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", alias("Mutable"))]
+struct HumanBean3 {
+    taste: String,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", alias)]
+struct HumanBean4 {
+    taste: String,
+}
+
+#[derive(Ensignal)]
+#[ensignal(alias(M = "Mutable"))]
+struct Human5 {
+    taste: String,
+}
+
+fn main() {}
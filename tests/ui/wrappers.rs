@@ -0,0 +1,30 @@
+use designal::{Designal, Ensignal};
+use std::borrow::Cow;
+use std::rc::Weak;
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", rebuild)]
+struct HumanBean {
+    friend: Weak<String>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human")]
+struct HumanBean1 {
+    #[designal(ignore, keep_cow)]
+    name: Cow<'static, str>,
+}
+
+#[derive(Designal)]
+#[designal(trim_start = "Human", keep_box, keep_box)]
+struct HumanBean2 {
+    name: Box<String>,
+}
+
+#[derive(Ensignal)]
+#[ensignal(keep_weak)]
+struct Human3 {
+    name: String,
+}
+
+fn main() {}